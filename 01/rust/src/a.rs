@@ -0,0 +1,9 @@
+use common::{AocError, AocResult, Answer};
+
+use crate::elves::Elves;
+
+pub fn main(elves: &Elves) -> AocResult<Answer> {
+    let top = elves.top();
+    let elf = top.first().ok_or(AocError::NoAnswer)?;
+    Answer::try_from(elf.calories)
+}
@@ -0,0 +1,33 @@
+use common::{AocError, AocResult, Answer};
+
+use crate::elves::Elves;
+use crate::TOP_K;
+
+pub fn main(elves: &Elves) -> AocResult<Answer> {
+    let top = elves.top();
+    if top.len() < TOP_K {
+        return Err(AocError::Solve(format!("Need at least {} elves, found {}", TOP_K, top.len())));
+    }
+    let total: u64 = top[..TOP_K].iter().map(|elf| elf.calories).sum();
+    Answer::try_from(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_too_few_elves() {
+        let elves = Elves::parse("1\n\n2\n", 3).unwrap();
+        assert_eq!(main(&elves), Err(AocError::solve("Need at least 3 elves, found 2")));
+        let elves = Elves::parse("1\n\n2\n\n3\n\n4\n", 3).unwrap();
+        assert_eq!(main(&elves), Ok(Answer::from(9)));
+    }
+
+    #[test]
+    fn test_total_too_big_for_an_answer() {
+        // The total fits in a u64, but not in an answer.
+        let elves = Elves::parse("4611686018427387904\n\n4611686018427387904\n\n1\n", 3).unwrap();
+        assert_eq!(main(&elves), Err(AocError::solve("9223372036854775809 is too big for an answer")));
+    }
+}
@@ -0,0 +1,286 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap};
+use std::io::BufRead;
+
+use common::{AocError, AocResult};
+
+/// One elf, by its position in the input (counting from 0), with the calories it carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: u64,
+}
+
+impl Ord for Elf {
+    /// More calories ranks higher; between elves carrying the same, the earlier one does.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.calories
+            .cmp(&other.calories)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Elf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A summary of how many calories the elves carry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    pub total: u64,
+    pub mean: f64,
+    pub median: u64,
+    pub min: u64,
+    pub max: u64,
+}
+
+/// How many leading binary digits of a calorie total `Elves` counts it by. Totals below
+/// 2^13 are counted exactly, and bigger ones rounded down by less than 1 part in 2^12.
+const SIGNIFICANT_BITS: u32 = 13;
+
+/// `calories`, rounded down to its first `SIGNIFICANT_BITS` binary digits.
+fn bucket(calories: u64) -> u64 {
+    let dropped = (u64::BITS - calories.leading_zeros()).saturating_sub(SIGNIFICANT_BITS);
+    calories >> dropped << dropped
+}
+
+/// The elves' calorie totals, added up one elf at a time. Only the top `k` elves are kept,
+/// along with how many elves carry totals in each of a bounded number of buckets, so the
+/// input never has to be held in memory.
+#[derive(Debug, Clone)]
+pub struct Elves {
+    k: usize,
+    // The top elves so far, lowest-ranked on top so that it's the one to drop.
+    top: BinaryHeap<Reverse<Elf>>,
+    // How many elves carry each total, rounded down by `bucket`. However many elves there
+    // are, there are at most 2^13 + 51 * 2^12 (about 217,000) buckets.
+    totals: BTreeMap<u64, usize>,
+    count: usize,
+    sum: u64,
+    min: u64,
+    max: u64,
+}
+
+impl Elves {
+    /// No elves yet, keeping track of the top `k`.
+    pub fn new(k: usize) -> Elves {
+        Elves {
+            k,
+            top: BinaryHeap::with_capacity(k + 1),
+            totals: BTreeMap::new(),
+            count: 0,
+            sum: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+
+    /// Read the elves from a calorie list: one number per line, with blank lines between
+    /// elves. Runs of blank lines, and blank lines at either end, don't make empty elves.
+    pub fn read<R: BufRead>(mut reader: R, k: usize) -> AocResult<Elves> {
+        let mut elves = Elves::new(k);
+        let mut line = String::new();
+        let mut line_number = 0;
+        // The calories of the elf being read, if it has any snacks yet.
+        let mut current: Option<u64> = None;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            line_number += 1;
+            let text = line.trim();
+            if text.is_empty() {
+                if let Some(calories) = current.take() {
+                    elves.add(calories)?;
+                }
+                continue;
+            }
+            let snack = text
+                .parse::<u64>()
+                .map_err(|_| AocError::parse(text, "Expected a number of calories").on_line(line_number, &line))?;
+            let calories = current.unwrap_or(0);
+            current = Some(calories.checked_add(snack).ok_or_else(|| {
+                AocError::parse(text, "Too many calories for one elf").on_line(line_number, &line)
+            })?);
+        }
+        if let Some(calories) = current {
+            elves.add(calories)?;
+        }
+        Ok(elves)
+    }
+
+    pub fn parse(contents: &str, k: usize) -> AocResult<Elves> {
+        Elves::read(contents.as_bytes(), k)
+    }
+
+    /// Add the next elf, carrying `calories` in all. Fails, leaving the elves as they were,
+    /// if the total over every elf would no longer fit in a `u64`.
+    pub fn add(&mut self, calories: u64) -> AocResult<()> {
+        self.sum = self
+            .sum
+            .checked_add(calories)
+            .ok_or_else(|| AocError::solve("Too many calories between the elves to add up"))?;
+        let elf = Elf { index: self.count, calories };
+        self.count += 1;
+        self.min = self.min.min(calories);
+        self.max = self.max.max(calories);
+        *self.totals.entry(bucket(calories)).or_insert(0) += 1;
+        if self.k == 0 {
+            return Ok(());
+        }
+        if self.top.len() < self.k {
+            self.top.push(Reverse(elf));
+        } else if let Some(mut lowest) = self.top.peek_mut() {
+            if elf > lowest.0 {
+                *lowest = Reverse(elf);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The top `k` elves (or all of them, if there are fewer), the most calories first.
+    pub fn top(&self) -> Vec<Elf> {
+        let mut top: Vec<Elf> = self.top.iter().map(|elf| elf.0).collect();
+        top.sort_by(|a, b| b.cmp(a));
+        top
+    }
+
+    /// How many calories the top `k` elves carry between them.
+    pub fn top_total(&self) -> u64 {
+        self.top.iter().map(|elf| elf.0.calories).sum()
+    }
+
+    /// The smallest total that at least `percent`% of the elves carry no more than (the
+    /// nearest-rank percentile), or `None` if there are no elves or `percent` is outside
+    /// 0 to 100. Totals of 2^13 and up are rounded down as `bucket` does.
+    pub fn percentile(&self, percent: f64) -> Option<u64> {
+        if self.count == 0 || !(0.0..=100.0).contains(&percent) {
+            return None;
+        }
+        let rank = ((percent / 100.0 * self.count as f64).ceil() as usize).max(1);
+        let mut seen = 0;
+        for (calories, elves) in &self.totals {
+            seen += elves;
+            if seen >= rank {
+                return Some(*calories);
+            }
+        }
+        None
+    }
+
+    /// The statistics over every elf, or `None` if there are none. With an even number of
+    /// elves, the median is the lower of the middle two, rounded as `percentile` does; the
+    /// rest are exact.
+    pub fn stats(&self) -> Option<Stats> {
+        Some(Stats {
+            elves: self.count,
+            total: self.sum,
+            mean: self.sum as f64 / self.count as f64,
+            median: self.percentile(50.0)?,
+            min: self.min,
+            max: self.max,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn test_top() {
+        let elves = Elves::parse(EXAMPLE, 3).unwrap();
+        assert_eq!(elves.count(), 5);
+        assert_eq!(
+            elves.top(),
+            vec![
+                Elf { index: 3, calories: 24000 },
+                Elf { index: 2, calories: 11000 },
+                Elf { index: 4, calories: 10000 },
+            ]
+        );
+        assert_eq!(elves.top_total(), 45000);
+        assert_eq!(Elves::parse(EXAMPLE, 1).unwrap().top_total(), 24000);
+        assert_eq!(Elves::parse(EXAMPLE, 10).unwrap().top().len(), 5);
+        assert_eq!(Elves::parse(EXAMPLE, 0).unwrap().top(), vec![]);
+    }
+
+    #[test]
+    fn test_ties_go_to_the_earlier_elf() {
+        let elves = Elves::parse("5\n\n7\n\n5\n\n5\n", 2).unwrap();
+        assert_eq!(elves.top(), vec![Elf { index: 1, calories: 7 }, Elf { index: 0, calories: 5 }]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let elves = Elves::parse(EXAMPLE, 1).unwrap();
+        let stats = elves.stats().unwrap();
+        assert_eq!(
+            stats,
+            Stats { elves: 5, total: 55000, mean: 11000.0, median: 10000, min: 4000, max: 24000 }
+        );
+        assert_eq!(elves.percentile(0.0), Some(4000));
+        assert_eq!(elves.percentile(20.0), Some(4000));
+        assert_eq!(elves.percentile(21.0), Some(6000));
+        assert_eq!(elves.percentile(100.0), Some(24000));
+        assert_eq!(elves.percentile(101.0), None);
+        assert_eq!(Elves::parse("", 3).unwrap().stats(), None);
+    }
+
+    #[test]
+    fn test_totals_are_bucketed() {
+        assert_eq!(bucket(8191), 8191);
+        assert_eq!(bucket(8193), 8192);
+        assert_eq!(bucket(u64::MAX), u64::MAX >> 51 << 51);
+        // A hundred thousand different totals, all within 2^17 of 10^9, share two buckets.
+        let mut elves = Elves::new(1);
+        for i in 0..100_000 {
+            elves.add(1_000_000_000 + i).unwrap();
+        }
+        assert_eq!(elves.totals.len(), 2);
+        let stats = elves.stats().unwrap();
+        assert_eq!((stats.min, stats.max), (1_000_000_000, 1_000_099_999));
+        // The median is 1,000,049,999, rounded down by less than 1 part in 2^12.
+        assert_eq!(stats.median, bucket(1_000_049_999));
+        assert!(1_000_049_999 - stats.median < 1_000_049_999 >> 12);
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let elves = Elves::parse("\n\n1\r\n2\r\n\r\n\r\n3\n\n", 5).unwrap();
+        assert_eq!(elves.top(), vec![Elf { index: 0, calories: 3 }, Elf { index: 1, calories: 3 }]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Elves::parse("1000\n\n20x0\n", 3).unwrap_err(),
+            AocError::parse("20x0", "Expected a number of calories").at(3, 1)
+        );
+        assert!(Elves::parse("18446744073709551615\n1\n", 1).is_err());
+    }
+
+    #[test]
+    fn test_total_overflow() {
+        // Each elf's calories fit, but not the total over both.
+        assert_eq!(
+            Elves::parse("18446744073709551615\n\n1\n", 2).unwrap_err(),
+            AocError::solve("Too many calories between the elves to add up")
+        );
+        let mut elves = Elves::new(2);
+        elves.add(u64::MAX).unwrap();
+        assert!(elves.add(1).is_err());
+        assert_eq!(elves.count(), 1);
+        assert_eq!(elves.top_total(), u64::MAX);
+    }
+}
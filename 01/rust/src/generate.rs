@@ -0,0 +1,20 @@
+use common::{Generator, Rng};
+
+use crate::Day01;
+
+/// `size` elves, each carrying a handful of snacks, as blank-line-separated groups.
+impl Generator for Day01 {
+    const DEFAULT_SIZE: usize = 250;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let groups: Vec<String> = (0..size.max(3))
+            .map(|_| {
+                let snacks: Vec<String> = (0..rng.range(1..=15))
+                    .map(|_| rng.range(1000..=60000).to_string())
+                    .collect();
+                snacks.join("\n")
+            })
+            .collect();
+        groups.join("\n\n") + "\n"
+    }
+}
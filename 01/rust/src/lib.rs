@@ -0,0 +1,33 @@
+mod a;
+mod b;
+pub mod elves;
+mod generate;
+mod reference;
+
+use common::{AocResult, Answer, Part, Solution};
+use elves::Elves;
+
+// Part b wants the top three elves, which covers part a's top one.
+const TOP_K: usize = 3;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Elves;
+
+    fn parse(contents: &str) -> AocResult<Self::Input> {
+        Elves::parse(contents, TOP_K)
+    }
+
+    fn part_a(input: &Self::Input) -> AocResult<Answer> {
+        a::main(input)
+    }
+
+    fn part_b(input: &Self::Input) -> AocResult<Answer> {
+        b::main(input)
+    }
+
+    fn reference(contents: &str, part: Part) -> AocResult<Answer> {
+        reference::solve(contents, part)
+    }
+}
@@ -1,62 +1,44 @@
 use std::fs::File;
-use std::path::Path;
-use std::io::{self, BufRead};
+use std::io::BufReader;
+use std::process::ExitCode;
 
+use common::{AocError, AocResult};
+use day01::elves::Elves;
+use day01::Day01;
 
-fn main() -> io::Result<()> {
+fn main() -> ExitCode {
     let args: Vec<String> = std::env::args().collect();
-
-    if args.len() != 3 {
-        println!("Usage: {} <a> <input_file>", args[0]);
-        println!("Where <a> is either 'a' or 'b', and <input_file> is the input file");
-        std::process::exit(1);
+    if args.get(1).map(|s| s.as_str()) == Some("top") {
+        return common::exit(top(&args));
     }
-    let part = &args[1];
-    let filename = &args[2];
-    println!("Reading from file: {}", filename);
-
-    // Open the file
-    let file = File::open(&Path::new(filename))?;
-
-    // Create a vector to store the lines
-    let mut lines: Vec<Vec<i32>> = Vec::new();
-    lines.push(Vec::new());
-
-    // Use a BufReader to read the file line by line
-    let reader = io::BufReader::new(file);
-
-    for line in reader.lines() {
-        let line = line?;
-        if line == "" {
-            lines.push(Vec::new());
-        } else {
-            let number = line.parse::<i32>().unwrap();
-            lines.last_mut().unwrap().push(number);
+    common::exit(common::run::<Day01>(args))
+}
 
-        }
+/// `top <k> <input>`: the top k elves, and statistics over all of them. The input is read a
+/// line at a time, so it can be bigger than memory.
+fn top(args: &[String]) -> AocResult<()> {
+    let usage = || AocError::Usage(format!("Usage: {} top <k> <input>", args[0]));
+    let [_, _, k, path] = args else {
+        return Err(usage());
+    };
+    let k: usize = k.parse().map_err(|_| usage())?;
+    let file = File::open(path).map_err(|e| AocError::Io(format!("{}: {}", path, e)))?;
+    let elves = Elves::read(BufReader::new(file), k)?;
+    for (rank, elf) in elves.top().iter().enumerate() {
+        println!("{:>4}. elf {} carries {}", rank + 1, elf.index + 1, elf.calories);
     }
-
-    // Sum up the vectors
-    let mut sums: Vec<i32> = Vec::new();
-    lines.iter().for_each(|line| {
-        let mut sum = 0;
-        line.iter().for_each(|number| {
-            sum += number;
-        });
-        sums.push(sum);
-    });
-
-    // Get the maximum sum
-    if part == "a" {
-        let max_sum = sums.iter().max().unwrap();
-        println!("The maximum sum is: {}", max_sum);
-    } else {
-        // Find the top 3 sums
-        sums.sort();
-        sums.reverse();
-        let total: i32 = sums[0..3].iter().sum();
-        println!("The total of the top 3 sums is: {}", total);
+    println!("The top {} carry {} between them", k, elves.top_total());
+    if let Some(stats) = elves.stats() {
+        println!(
+            "{} elves carry {} in all: mean {:.1}, median {}, 90th percentile {}, min {}, max {}",
+            stats.elves,
+            stats.total,
+            stats.mean,
+            stats.median,
+            elves.percentile(90.0).unwrap_or(stats.max),
+            stats.min,
+            stats.max
+        );
     }
-
     Ok(())
 }
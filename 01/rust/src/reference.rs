@@ -0,0 +1,19 @@
+use common::{AocError, AocResult, Answer, Part};
+
+/// Add up every elf's calories, sort them all and take the biggest.
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let mut totals: Vec<u64> = contents
+        .split("\n\n")
+        .filter(|group| !group.trim().is_empty())
+        .map(|group| group.lines().map(|line| line.trim().parse::<u64>()).sum())
+        .collect::<Result<_, _>>()?;
+    totals.sort_by(|a, b| b.cmp(a));
+    let count = match part {
+        Part::A => 1,
+        Part::B => 3,
+    };
+    if totals.len() < count {
+        return Err(AocError::NoAnswer);
+    }
+    Answer::try_from(totals[..count].iter().sum::<u64>())
+}
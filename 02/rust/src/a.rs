@@ -0,0 +1,33 @@
+use common::{AocResult, Answer};
+
+use crate::game::Game;
+use crate::rps::{Code, Row, Throw};
+
+/// Read the second column as what to throw.
+pub fn main(rows: &[Row]) -> AocResult<Answer> {
+    let game = Game::classic();
+    let scores = rows
+        .iter()
+        .map(|row| game.play(decode_code(row.code).index(), row.opponent.index()));
+    Ok(Answer::from(scores.sum::<i32>()))
+}
+
+fn decode_code(code: Code) -> Throw {
+    match code {
+        Code::X => Throw::Rock,
+        Code::Y => Throw::Paper,
+        Code::Z => Throw::Scissors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rps;
+
+    #[test]
+    fn test_example() {
+        let rows = rps::parse("A Y\nB X\nC Z").unwrap();
+        assert_eq!(main(&rows), Ok(Answer::from(15)));
+    }
+}
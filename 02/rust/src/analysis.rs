@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::game::Game;
+use crate::rps::{Outcome, Row};
+
+/// The ways to give X, Y and Z three different meanings. The first is the one the puzzle
+/// uses, in both interpretations.
+const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+/// What X, Y and Z say under the identity permutation, read as outcomes.
+const OUTCOMES: [Outcome; 3] = [Outcome::Opponent, Outcome::Tie, Outcome::You];
+
+/// How to read the guide's second column: as the throw to make (part a), or as how the
+/// round should end (part b).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    Throw,
+    Outcome,
+}
+
+/// How many rounds you win, tie and lose.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub ties: usize,
+    pub losses: usize,
+}
+
+impl Tally {
+    pub(crate) fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::You => self.wins += 1,
+            Outcome::Tie => self.ties += 1,
+            Outcome::Opponent => self.losses += 1,
+        }
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} won, {} tied, {} lost", self.wins, self.ties, self.losses)
+    }
+}
+
+/// The guide scored under one reading of X, Y and Z.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub interpretation: Interpretation,
+    // What X, Y and Z stand for: throws in the classic game, or indexes into OUTCOMES.
+    permutation: [usize; 3],
+    pub total: i32,
+    pub tally: Tally,
+}
+
+impl Mapping {
+    /// Whether this is how the puzzle reads the guide.
+    pub fn is_puzzle(&self) -> bool {
+        self.permutation == PERMUTATIONS[0]
+    }
+
+    /// Describe the mapping, as in "X=Rock Y=Paper Z=Scissors".
+    pub fn describe(&self, game: &Game) -> String {
+        let meanings = self.permutation.iter().map(|&i| match self.interpretation {
+            Interpretation::Throw => game.name(i).to_string(),
+            Interpretation::Outcome => match OUTCOMES[i] {
+                Outcome::You => String::from("win"),
+                Outcome::Tie => String::from("tie"),
+                Outcome::Opponent => String::from("lose"),
+            },
+        });
+        ["X", "Y", "Z"]
+            .iter()
+            .zip(meanings)
+            .map(|(code, meaning)| format!("{}={}", code, meaning))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The guide scored under all six mappings, for each interpretation, in the classic game.
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub game: Game,
+    pub mappings: Vec<Mapping>,
+}
+
+pub fn analyze(rows: &[Row]) -> Analysis {
+    let game = Game::classic();
+    let mut mappings = Vec::new();
+    for interpretation in [Interpretation::Throw, Interpretation::Outcome] {
+        for permutation in PERMUTATIONS {
+            mappings.push(score(&game, rows, interpretation, permutation));
+        }
+    }
+    Analysis { game, mappings }
+}
+
+fn score(game: &Game, rows: &[Row], interpretation: Interpretation, permutation: [usize; 3]) -> Mapping {
+    let mut total = 0;
+    let mut tally = Tally::default();
+    for row in rows {
+        let opponent = row.opponent.index();
+        let meaning = permutation[row.code as usize];
+        let (you, outcome) = match interpretation {
+            Interpretation::Throw => (meaning, game.outcome(meaning, opponent)),
+            Interpretation::Outcome => {
+                let outcome = OUTCOMES[meaning];
+                // Every outcome is possible against every throw in the classic game.
+                (game.throw_for(opponent, outcome).unwrap(), outcome)
+            }
+        };
+        total += game.score(you, outcome);
+        tally.add(outcome);
+    }
+    Mapping { interpretation, permutation, total, tally }
+}
+
+impl Analysis {
+    pub fn mappings(&self, interpretation: Interpretation) -> impl Iterator<Item = &Mapping> {
+        self.mappings
+            .iter()
+            .filter(move |mapping| mapping.interpretation == interpretation)
+    }
+
+    /// The highest-scoring mapping; the earliest one if several tie.
+    pub fn best(&self, interpretation: Interpretation) -> &Mapping {
+        self.mappings(interpretation)
+            .reduce(|best, mapping| if mapping.total > best.total { mapping } else { best })
+            .unwrap()
+    }
+
+    /// The lowest-scoring mapping; the earliest one if several tie.
+    pub fn worst(&self, interpretation: Interpretation) -> &Mapping {
+        self.mappings(interpretation)
+            .reduce(|worst, mapping| if mapping.total < worst.total { mapping } else { worst })
+            .unwrap()
+    }
+
+    /// The mapping the puzzle uses.
+    pub fn puzzle(&self, interpretation: Interpretation) -> &Mapping {
+        self.mappings(interpretation)
+            .find(|mapping| mapping.is_puzzle())
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rps;
+
+    #[test]
+    fn test_example() {
+        let analysis = analyze(&rps::parse("A Y\nB X\nC Z").unwrap());
+        assert_eq!(analysis.mappings.len(), 12);
+
+        let puzzle = analysis.puzzle(Interpretation::Throw);
+        assert_eq!(puzzle.total, 15);
+        assert_eq!(puzzle.tally, Tally { wins: 1, ties: 1, losses: 1 });
+        assert_eq!(puzzle.describe(&analysis.game), "X=Rock Y=Paper Z=Scissors");
+        let best = analysis.best(Interpretation::Throw);
+        // X=Scissors Y=Paper Z=Rock wins every round: 6 + 2, 6 + 3 and 6 + 1.
+        assert_eq!(best.describe(&analysis.game), "X=Scissors Y=Paper Z=Rock");
+        assert_eq!(best.total, 24);
+        assert_eq!(best.tally, Tally { wins: 3, ties: 0, losses: 0 });
+        assert_eq!(analysis.worst(Interpretation::Throw).total, 6);
+
+        let puzzle = analysis.puzzle(Interpretation::Outcome);
+        assert_eq!(puzzle.total, 12);
+        assert_eq!(puzzle.describe(&analysis.game), "X=lose Y=tie Z=win");
+        // Each code is used once, so every outcome mapping wins, ties and loses once.
+        for mapping in analysis.mappings(Interpretation::Outcome) {
+            assert_eq!(mapping.tally, Tally { wins: 1, ties: 1, losses: 1 });
+        }
+    }
+
+    #[test]
+    fn test_best_and_worst_cover_every_mapping() {
+        let analysis = analyze(&rps::parse("A X\nA X\nB Z\nC Y\nC X").unwrap());
+        for interpretation in [Interpretation::Throw, Interpretation::Outcome] {
+            let best = analysis.best(interpretation).total;
+            let worst = analysis.worst(interpretation).total;
+            for mapping in analysis.mappings(interpretation) {
+                assert!((worst..=best).contains(&mapping.total));
+                assert_eq!(mapping.tally.wins + mapping.tally.ties + mapping.tally.losses, 5);
+            }
+        }
+    }
+}
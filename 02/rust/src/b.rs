@@ -0,0 +1,39 @@
+use common::{AocError, AocResult, Answer};
+
+use crate::game::Game;
+use crate::rps::{Code, Outcome, Row};
+
+/// Read the second column as how the round should end.
+pub fn main(rows: &[Row]) -> AocResult<Answer> {
+    let game = Game::classic();
+    let mut total = 0;
+    for row in rows {
+        let opponent = row.opponent.index();
+        let outcome = decode_code(row.code);
+        let you = game
+            .throw_for(opponent, outcome)
+            .ok_or_else(|| AocError::Solve(format!("No throw gets {:?} against {}", outcome, game.name(opponent))))?;
+        total += game.score(you, outcome);
+    }
+    Ok(Answer::from(total))
+}
+
+fn decode_code(code: Code) -> Outcome {
+    match code {
+        Code::X => Outcome::Opponent,
+        Code::Y => Outcome::Tie,
+        Code::Z => Outcome::You,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rps;
+
+    #[test]
+    fn test_example() {
+        let rows = rps::parse("A Y\nB X\nC Z").unwrap();
+        assert_eq!(main(&rows), Ok(Answer::from(12)));
+    }
+}
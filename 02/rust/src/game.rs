@@ -0,0 +1,240 @@
+use common::{AocError, AocResult};
+
+use crate::rps::{Outcome, Throw};
+
+/// The rules of a rock-paper-scissors style game: which throws there are, which beats which,
+/// and what a round scores. Throws are named by their position in the list of throws.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    // beats[a][b] is true when throw a beats throw b.
+    beats: Vec<Vec<bool>>,
+    // What throwing each throw scores, whatever happens.
+    throw_scores: Vec<i32>,
+    win: i32,
+    tie: i32,
+    loss: i32,
+}
+
+impl Game {
+    /// A game with the given throws, where each `(winner, loser)` pair says that one throw
+    /// beats another; any other pair of different throws is a tie. Rounds score as in the
+    /// puzzle: the throw's position counting from 1, plus 6 for a win, 3 for a tie and 0 for
+    /// a loss. There must be at least one throw.
+    pub fn new(names: &[&str], beats: &[(usize, usize)]) -> AocResult<Game> {
+        let n = names.len();
+        if n == 0 {
+            return Err(AocError::solve("A game needs at least one throw"));
+        }
+        let mut table = vec![vec![false; n]; n];
+        for &(winner, loser) in beats {
+            if winner >= n || loser >= n {
+                return Err(AocError::Solve(format!(
+                    "No throw {} in a game of {} throws",
+                    winner.max(loser),
+                    n
+                )));
+            }
+            if winner == loser {
+                return Err(AocError::Solve(format!("{} can't beat itself", names[winner])));
+            }
+            if table[loser][winner] {
+                return Err(AocError::Solve(format!(
+                    "{} and {} can't both beat each other",
+                    names[winner], names[loser]
+                )));
+            }
+            table[winner][loser] = true;
+        }
+        Ok(Game {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats: table,
+            throw_scores: (1..=n as i32).collect(),
+            win: 6,
+            tie: 3,
+            loss: 0,
+        })
+    }
+
+    /// Rock, paper and scissors, in the order of `Throw`.
+    pub fn classic() -> Game {
+        Game::new(&["Rock", "Paper", "Scissors"], &[(0, 2), (1, 0), (2, 1)]).unwrap()
+    }
+
+    /// Rock-Paper-Scissors-Lizard-Spock, which starts with the classic game's throws.
+    pub fn rpsls() -> Game {
+        let names = ["Rock", "Paper", "Scissors", "Lizard", "Spock"];
+        let beats = [
+            (0, 2), (0, 3), // Rock crushes scissors and lizard
+            (1, 0), (1, 4), // Paper covers rock and disproves Spock
+            (2, 1), (2, 3), // Scissors cut paper and decapitate lizard
+            (3, 1), (3, 4), // Lizard eats paper and poisons Spock
+            (4, 0), (4, 2), // Spock vaporizes rock and smashes scissors
+        ];
+        Game::new(&names, &beats).unwrap()
+    }
+
+    /// A fair game of `n` throws, where each throw beats the `(n - 1) / 2` throws before it
+    /// (wrapping around), so every throw beats as many as it loses to. `n` must be odd; 3 is
+    /// the classic game.
+    pub fn cyclic(n: usize) -> AocResult<Game> {
+        if n.is_multiple_of(2) {
+            return Err(AocError::Solve(format!("A fair cyclic game needs an odd number of throws, not {}", n)));
+        }
+        let names: Vec<String> = (0..n).map(|i| format!("Throw {}", i + 1)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let beats: Vec<(usize, usize)> = (0..n)
+            .flat_map(|winner| (1..=(n - 1) / 2).map(move |back| (winner, (winner + n - back) % n)))
+            .collect();
+        Game::new(&names, &beats)
+    }
+
+    /// The same game, scoring rounds differently.
+    pub fn with_scores(self, throw_scores: Vec<i32>, win: i32, tie: i32, loss: i32) -> AocResult<Game> {
+        if throw_scores.len() != self.len() {
+            return Err(AocError::Solve(format!(
+                "Expected {} throw scores, found {}",
+                self.len(),
+                throw_scores.len()
+            )));
+        }
+        Ok(Game { throw_scores, win, tie, loss, ..self })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, throw: usize) -> &str {
+        &self.names[throw]
+    }
+
+    pub fn beats(&self, you: usize, opponent: usize) -> bool {
+        self.beats[you][opponent]
+    }
+
+    pub fn outcome(&self, you: usize, opponent: usize) -> Outcome {
+        if self.beats(you, opponent) {
+            Outcome::You
+        } else if self.beats(opponent, you) {
+            Outcome::Opponent
+        } else {
+            Outcome::Tie
+        }
+    }
+
+    /// Every throw that gets `outcome` against `opponent`, in order.
+    pub fn throws_for(&self, opponent: usize, outcome: Outcome) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&you| self.outcome(you, opponent) == outcome)
+            .collect()
+    }
+
+    /// The first throw that gets `outcome` against `opponent`, if there is one.
+    pub fn throw_for(&self, opponent: usize, outcome: Outcome) -> Option<usize> {
+        (0..self.len()).find(|&you| self.outcome(you, opponent) == outcome)
+    }
+
+    /// What you score for a round where you throw `you` and get `outcome`.
+    pub fn score(&self, you: usize, outcome: Outcome) -> i32 {
+        let outcome_score = match outcome {
+            Outcome::You => self.win,
+            Outcome::Opponent => self.loss,
+            Outcome::Tie => self.tie,
+        };
+        self.throw_scores[you] + outcome_score
+    }
+
+    /// What you score for throwing `you` against `opponent`.
+    pub fn play(&self, you: usize, opponent: usize) -> i32 {
+        self.score(you, self.outcome(you, opponent))
+    }
+}
+
+impl Throw {
+    /// Where this throw is in `Game::classic` (and `Game::rpsls`).
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic() {
+        let game = Game::classic();
+        let (rock, paper, scissors) = (Throw::Rock.index(), Throw::Paper.index(), Throw::Scissors.index());
+        assert_eq!(game.name(paper), "Paper");
+        assert_eq!(game.outcome(rock, scissors), Outcome::You);
+        assert_eq!(game.outcome(rock, paper), Outcome::Opponent);
+        assert_eq!(game.outcome(scissors, scissors), Outcome::Tie);
+        assert_eq!(game.play(paper, rock), 8);
+        assert_eq!(game.play(rock, paper), 1);
+        assert_eq!(game.play(scissors, scissors), 6);
+        assert_eq!(game.throw_for(rock, Outcome::Opponent), Some(scissors));
+        let cyclic = Game::cyclic(3).unwrap();
+        for you in 0..3 {
+            for opponent in 0..3 {
+                assert_eq!(cyclic.play(you, opponent), game.play(you, opponent));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let game = Game::rpsls();
+        for you in 0..game.len() {
+            assert_eq!(game.throws_for(you, Outcome::You).len(), 2);
+            assert_eq!(game.throws_for(you, Outcome::Opponent).len(), 2);
+            assert_eq!(game.throws_for(you, Outcome::Tie), vec![you]);
+        }
+        // Spock vaporizes rock; lizard poisons Spock.
+        assert_eq!(game.outcome(4, 0), Outcome::You);
+        assert_eq!(game.outcome(4, 3), Outcome::Opponent);
+        assert_eq!(game.throws_for(0, Outcome::You), vec![1, 4]);
+        // The classic throws play as they do in the classic game.
+        let classic = Game::classic();
+        for you in 0..3 {
+            for opponent in 0..3 {
+                assert_eq!(game.outcome(you, opponent), classic.outcome(you, opponent));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cyclic() {
+        let game = Game::cyclic(7).unwrap();
+        for you in 0..7 {
+            assert_eq!(game.throws_for(you, Outcome::You).len(), 3);
+            assert_eq!(game.throws_for(you, Outcome::Opponent).len(), 3);
+        }
+        assert!(game.beats(0, 6));
+        assert!(game.beats(0, 4));
+        assert!(!game.beats(0, 3));
+        assert!(Game::cyclic(4).is_err());
+        assert_eq!(Game::cyclic(1).unwrap().outcome(0, 0), Outcome::Tie);
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        assert_eq!(Game::new(&[], &[]), Err(AocError::solve("A game needs at least one throw")));
+        assert!(Game::new(&["A", "B"], &[(0, 0)]).is_err());
+        assert!(Game::new(&["A", "B"], &[(0, 1), (1, 0)]).is_err());
+        assert!(Game::new(&["A", "B"], &[(0, 2)]).is_err());
+        assert!(Game::classic().with_scores(vec![1, 2], 6, 3, 0).is_err());
+    }
+
+    #[test]
+    fn test_scores() {
+        let game = Game::classic().with_scores(vec![0, 0, 10], 1, 0, -1).unwrap();
+        assert_eq!(game.play(2, 0), 9);
+        assert_eq!(game.play(0, 2), 1);
+        assert_eq!(game.play(1, 1), 0);
+    }
+}
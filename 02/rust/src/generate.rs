@@ -0,0 +1,14 @@
+use common::{Generator, Rng};
+
+use crate::Day02;
+
+/// `size` rounds of the strategy guide.
+impl Generator for Day02 {
+    const DEFAULT_SIZE: usize = 2500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| format!("{} {}\n", rng.choose(&['A', 'B', 'C']), rng.choose(&['X', 'Y', 'Z'])))
+            .collect()
+    }
+}
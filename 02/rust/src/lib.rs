@@ -0,0 +1,33 @@
+mod a;
+pub mod analysis;
+mod b;
+pub mod game;
+mod generate;
+mod reference;
+pub mod rps;
+pub mod tournament;
+
+use common::{AocResult, Answer, Part, Solution};
+use rps::Row;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Row>;
+
+    fn parse(contents: &str) -> AocResult<Self::Input> {
+        rps::parse(contents)
+    }
+
+    fn part_a(input: &Self::Input) -> AocResult<Answer> {
+        a::main(input)
+    }
+
+    fn part_b(input: &Self::Input) -> AocResult<Answer> {
+        b::main(input)
+    }
+
+    fn reference(contents: &str, part: Part) -> AocResult<Answer> {
+        reference::solve(contents, part)
+    }
+}
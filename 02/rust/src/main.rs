@@ -1,165 +1,73 @@
-use core::{str::Lines, panic};
+use std::process::ExitCode;
 
-enum Throw {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-enum Outcome {
-    You,
-    Opponent,
-    Tie,
-}
+use common::{AocError, AocResult};
+use day02::analysis::{self, Interpretation};
+use day02::game::Game;
+use day02::tournament;
+use day02::Day02;
 
-fn main() {
+fn main() -> ExitCode {
     let args: Vec<String> = std::env::args().collect();
-    if args.len() != 3 {
-        println!("Usage: {} <a/b> <input>", args[0]);
-        std::process::exit(1);
+    if args.get(1).map(|s| s.as_str()) == Some("analyze") {
+        return common::exit(analyze(&args));
     }
-    let part = args[1].as_str();
-    let input_file = args[2].as_str();
-    let contents = std::fs::read_to_string(input_file).unwrap();
-    let lines = contents.lines();
-    let score = match part {
-        "a" => do_a(lines),
-        "b" => do_b(lines),
-        _ => panic!("Invalid part"),
-    };
-    println!("Your score is {}", score.unwrap());
-}
-
-fn do_a(input: Lines) -> Result::<i32, String> {
-    let turns: Vec<TurnA> = match input.map(|row| decode_row_a(row)).collect() {
-        Ok(turns) => turns,
-        Err(e) => return Err(e),
-    };
-    let scores = turns.iter().map(|turn| turn.score());
-    Ok(scores.sum())
+    if args.get(1).map(|s| s.as_str()) == Some("tournament") {
+        return common::exit(run_tournament(&args));
+    }
+    common::exit(common::run::<Day02>(args))
 }
 
-fn decode_row_a(row: &str) -> Result::<TurnA, String> {
-    let parts = row.split(" ").collect::<Vec<_>>();
-
-    let opponent = match parts[0] {
-        "A" => Throw::Rock,
-        "B" => Throw::Paper,
-        "C" => Throw::Scissors,
-        _ => return Err(("Invalid input").to_string()),
-    };
-
-    let you = match parts[1] {
-        "X" => Throw::Rock,
-        "Y" => Throw::Paper,
-        "Z" => Throw::Scissors,
-        _ => return Err(("Invalid input").to_string()),
+/// `analyze <input>`: the guide's score under every way of reading X, Y and Z, to see how
+/// good the elf's guide really was.
+fn analyze(args: &[String]) -> AocResult<()> {
+    let [_, _, path] = args else {
+        return Err(AocError::Usage(format!("Usage: {} analyze <input>", args[0])));
     };
-
-    Ok(TurnA { opponent, you })
-}
-
-struct TurnA {
-    // The choices made by you and an opponent in a round of RPS
-    opponent: Throw,
-    you: Throw,
-}
-
-impl TurnA {
-    fn outcome(&self) -> Outcome {
-        match (&self.you, &self.opponent) {
-            (Throw::Rock, Throw::Scissors) => Outcome::You,
-            (Throw::Rock, Throw::Paper) => Outcome::Opponent,
-            (Throw::Paper, Throw::Rock) => Outcome::You,
-            (Throw::Paper, Throw::Scissors) => Outcome::Opponent,
-            (Throw::Scissors, Throw::Paper) => Outcome::You,
-            (Throw::Scissors, Throw::Rock) => Outcome::Opponent,
-            _ => Outcome::Tie,
+    let contents = std::fs::read_to_string(path).map_err(|e| AocError::Io(format!("{}: {}", path, e)))?;
+    let analysis = analysis::analyze(&day02::rps::parse(&contents)?);
+    for (interpretation, heading) in [
+        (Interpretation::Throw, "Read as the throw to make:"),
+        (Interpretation::Outcome, "Read as how the round should end:"),
+    ] {
+        println!("{}", heading);
+        let best = analysis.best(interpretation);
+        let worst = analysis.worst(interpretation);
+        for mapping in analysis.mappings(interpretation) {
+            let mut notes = Vec::new();
+            if mapping.is_puzzle() {
+                notes.push("the puzzle's");
+            }
+            if mapping == best {
+                notes.push("best");
+            }
+            if mapping == worst {
+                notes.push("worst");
+            }
+            let line = format!(
+                "  {}  {:>6}  ({})  {}",
+                mapping.describe(&analysis.game),
+                mapping.total,
+                mapping.tally,
+                notes.join(", ")
+            );
+            println!("{}", line.trim_end());
         }
     }
-
-    fn score(&self) -> i32 {
-        let outcome_score = match self.outcome() {
-            Outcome::You => 6,
-            Outcome::Opponent => 0,
-            Outcome::Tie => 3,
-        };
-        let throw_score = match self.you {
-            Throw::Rock => 1,
-            Throw::Paper => 2,
-            Throw::Scissors => 3,
-        };
-        outcome_score + throw_score
-    }
-}
-
-fn do_b(input: Lines) -> Result::<i32, String> {
-    let turns: Vec<TurnB> = match input.map(|row| decode_row_b(row)).collect() {
-        Ok(turns) => turns,
-        Err(e) => return Err(e),
-    };
-
-    let scores = turns.iter().map(|turn| turn.score());
-    Ok(scores.sum())
+    Ok(())
 }
 
-fn decode_row_b(row: &str) -> Result::<TurnB, String> {
-    let parts = row.split(" ").collect::<Vec<_>>();
-    
-    let opponent = match parts[0] {
-        "A" => Throw::Rock,
-        "B" => Throw::Paper,
-        "C" => Throw::Scissors,
-        _ => return Err(("Invalid input").to_string()),
-    };
-
-    let outcome = match parts[1] {
-        "X" => Outcome::Opponent,
-        "Y" => Outcome::Tie,
-        "Z" => Outcome::You,
-        _ => return Err(("Invalid input").to_string()),
+/// `tournament <rounds> [seed]`: every bot plays every other for `rounds` rounds, and the
+/// leaderboard ranks them by points.
+fn run_tournament(args: &[String]) -> AocResult<()> {
+    let usage = || AocError::Usage(format!("Usage: {} tournament <rounds> [seed]", args[0]));
+    let (rounds, seed) = match args {
+        [_, _, rounds] => (rounds, "0"),
+        [_, _, rounds, seed] => (rounds, seed.as_str()),
+        _ => return Err(usage()),
     };
-
-    Ok(TurnB::new(opponent, outcome))
-}
-
-struct TurnB {
-    // The choices made by you and an opponent in a round of RPS
-    you: Throw,
-    outcome: Outcome,
+    let rounds: usize = rounds.parse().map_err(|_| usage())?;
+    let seed: u64 = seed.parse().map_err(|_| usage())?;
+    let leaderboard = tournament::round_robin(&Game::classic(), &mut tournament::bots(seed), rounds)?;
+    print!("{}", leaderboard);
+    Ok(())
 }
-
-impl TurnB {
-    pub fn new(opponent: Throw, outcome: Outcome) -> TurnB {
-        // Determine what you should throw
-        let you = match (&opponent, &outcome) {
-            (Throw::Rock, Outcome::You) => Throw::Paper,
-            (Throw::Rock, Outcome::Opponent) => Throw::Scissors,
-            (Throw::Rock, Outcome::Tie) => Throw::Rock,
-            (Throw::Paper, Outcome::You) => Throw::Scissors,
-            (Throw::Paper, Outcome::Opponent) => Throw::Rock,
-            (Throw::Paper, Outcome::Tie) => Throw::Paper,
-            (Throw::Scissors, Outcome::You) => Throw::Rock,
-            (Throw::Scissors, Outcome::Opponent) => Throw::Paper,
-            (Throw::Scissors, Outcome::Tie) => Throw::Scissors,
-        };
-        TurnB {
-            outcome: outcome,
-            you: you,
-        }
-    }
-
-    fn score(&self) -> i32 {
-        let outcome_score = match self.outcome {
-            Outcome::You => 6,
-            Outcome::Opponent => 0,
-            Outcome::Tie => 3,
-        };
-        let throw_score = match self.you {
-            Throw::Rock => 1,
-            Throw::Paper => 2,
-            Throw::Scissors => 3,
-        };
-        outcome_score + throw_score
-    }
-}
\ No newline at end of file
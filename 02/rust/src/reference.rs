@@ -0,0 +1,30 @@
+use common::{AocError, AocResult, Answer, Part};
+
+/// Every round's score, written out: the shape you play plus the outcome.
+const SCORES_A: [(&str, i32); 9] = [
+    ("A X", 1 + 3), ("A Y", 2 + 6), ("A Z", 3),
+    ("B X", 1), ("B Y", 2 + 3), ("B Z", 3 + 6),
+    ("C X", 1 + 6), ("C Y", 2), ("C Z", 3 + 3),
+];
+/// The same, when X, Y and Z say how the round needs to end.
+const SCORES_B: [(&str, i32); 9] = [
+    ("A X", 3), ("A Y", 1 + 3), ("A Z", 2 + 6),
+    ("B X", 1), ("B Y", 2 + 3), ("B Z", 3 + 6),
+    ("C X", 2), ("C Y", 3 + 3), ("C Z", 1 + 6),
+];
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let scores = match part {
+        Part::A => &SCORES_A,
+        Part::B => &SCORES_B,
+    };
+    let mut total = 0;
+    for line in contents.lines() {
+        let (_, score) = scores
+            .iter()
+            .find(|(round, _)| *round == line)
+            .ok_or_else(|| AocError::parse(line, "Unknown round"))?;
+        total += score;
+    }
+    Ok(Answer::from(total))
+}
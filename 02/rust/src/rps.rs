@@ -0,0 +1,74 @@
+use common::{parse_lines, AocError, AocResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Throw {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    You,
+    Opponent,
+    Tie,
+}
+
+/// The second column of the strategy guide. Parts A and B disagree on what it means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Code {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub opponent: Throw,
+    pub code: Code,
+}
+
+pub fn parse(contents: &str) -> AocResult<Vec<Row>> {
+    parse_lines(contents, decode_row)
+}
+
+fn decode_row(row: &str) -> AocResult<Row> {
+    let parts = row.split(' ').collect::<Vec<_>>();
+    if parts.len() != 2 {
+        return Err(AocError::parse("", "Expected two columns"));
+    }
+
+    let opponent = match parts[0] {
+        "A" => Throw::Rock,
+        "B" => Throw::Paper,
+        "C" => Throw::Scissors,
+        other => return Err(AocError::parse(other, "Expected A, B or C")),
+    };
+
+    let code = match parts[1] {
+        "X" => Code::X,
+        "Y" => Code::Y,
+        "Z" => Code::Z,
+        other => return Err(AocError::parse(other, "Expected X, Y or Z")),
+    };
+
+    Ok(Row { opponent, code })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let rows = parse("A Y\nB X\nC Z").unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], Row { opponent: Throw::Rock, code: Code::Y });
+        assert_eq!(rows[2], Row { opponent: Throw::Scissors, code: Code::Z });
+        assert_eq!(
+            parse("A Y\nA W"),
+            Err(AocError::parse("W", "Expected X, Y or Z").at(2, 3))
+        );
+        assert!(parse("A").is_err());
+    }
+}
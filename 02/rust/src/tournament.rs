@@ -0,0 +1,278 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use common::{AocError, AocResult, Rng};
+
+use crate::analysis::Tally;
+use crate::game::Game;
+use crate::rps::{Outcome, Throw};
+
+/// One round of a match, from one player's side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub you: usize,
+    pub opponent: usize,
+}
+
+/// A bot that plays a game, one throw at a time.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// The next throw, given the rounds of this match so far.
+    fn throw(&mut self, game: &Game, history: &[Round]) -> usize;
+
+    /// Get ready for a new match.
+    fn reset(&mut self) {}
+}
+
+/// Rock, every time.
+pub struct AlwaysRock;
+
+impl Strategy for AlwaysRock {
+    fn name(&self) -> String {
+        String::from("always rock")
+    }
+
+    fn throw(&mut self, _game: &Game, _history: &[Round]) -> usize {
+        Throw::Rock.index()
+    }
+}
+
+/// Each throw in turn.
+pub struct Cycle;
+
+impl Strategy for Cycle {
+    fn name(&self) -> String {
+        String::from("cycle")
+    }
+
+    fn throw(&mut self, game: &Game, history: &[Round]) -> usize {
+        history.len() % game.len()
+    }
+}
+
+/// Whatever beats the opponent's most common throw so far (the earliest, if several are
+/// as common), starting with rock.
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        String::from("frequency counter")
+    }
+
+    fn throw(&mut self, game: &Game, history: &[Round]) -> usize {
+        let mut counts = vec![0; game.len()];
+        for round in history {
+            counts[round.opponent] += 1;
+        }
+        let favourite = (0..game.len())
+            .rev()
+            .max_by_key(|&throw| counts[throw])
+            .filter(|&throw| counts[throw] > 0);
+        favourite
+            .and_then(|throw| game.throw_for(throw, Outcome::You))
+            .unwrap_or(Throw::Rock.index())
+    }
+}
+
+/// Whatever the opponent threw last, starting with rock.
+pub struct CopyLast;
+
+impl Strategy for CopyLast {
+    fn name(&self) -> String {
+        String::from("copy last")
+    }
+
+    fn throw(&mut self, _game: &Game, history: &[Round]) -> usize {
+        history.last().map_or(Throw::Rock.index(), |round| round.opponent)
+    }
+}
+
+/// A random throw each round. Every match starts again from the seed.
+pub struct Random {
+    seed: u64,
+    rng: Rng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { seed, rng: Rng::new(seed) }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random (seed {})", self.seed)
+    }
+
+    fn throw(&mut self, game: &Game, _history: &[Round]) -> usize {
+        self.rng.below(game.len())
+    }
+
+    fn reset(&mut self) {
+        self.rng = Rng::new(self.seed);
+    }
+}
+
+/// Every bot, with the random one seeded by `seed`.
+pub fn bots(seed: u64) -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(AlwaysRock),
+        Box::new(Cycle),
+        Box::new(FrequencyCounter),
+        Box::new(CopyLast),
+        Box::new(Random::new(seed)),
+    ]
+}
+
+/// How one player did over the whole tournament. Points are scored as in the puzzle, and
+/// the tally counts rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub points: i32,
+    pub tally: Tally,
+}
+
+impl Standing {
+    fn new(name: String) -> Standing {
+        Standing { name, points: 0, tally: Tally::default() }
+    }
+
+    fn add(&mut self, game: &Game, round: Round) {
+        let outcome = game.outcome(round.you, round.opponent);
+        self.points += game.score(round.you, outcome);
+        self.tally.add(outcome);
+    }
+}
+
+/// The standings, most points first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard(pub Vec<Standing>);
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.0.iter().map(|standing| standing.name.len()).max().unwrap_or(0);
+        for (rank, standing) in self.0.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>3}. {:<width$}  {:>8} points  {}",
+                rank + 1,
+                standing.name,
+                standing.points,
+                standing.tally,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Play `rounds` rounds between each pair of players. Players with as many points keep their
+/// order. Fails up front if there are so many rounds that a player's points might not fit in
+/// an `i32`.
+pub fn round_robin(game: &Game, players: &mut [Box<dyn Strategy>], rounds: usize) -> AocResult<Leaderboard> {
+    let most_per_round = (0..game.len())
+        .flat_map(|you| [Outcome::You, Outcome::Tie, Outcome::Opponent].map(|outcome| game.score(you, outcome)))
+        .map(i32::unsigned_abs)
+        .max()
+        .unwrap_or(0);
+    let most_points = (rounds as u64)
+        .checked_mul(players.len().saturating_sub(1) as u64)
+        .and_then(|n| n.checked_mul(most_per_round as u64));
+    if most_points.is_none_or(|points| points > i32::MAX as u64) {
+        return Err(AocError::Solve(format!("Too many rounds to keep score: {}", rounds)));
+    }
+    let mut standings: Vec<Standing> = players.iter().map(|player| Standing::new(player.name())).collect();
+    for first in 0..players.len() {
+        for second in first + 1..players.len() {
+            let (left, right) = players.split_at_mut(second);
+            let (a, b) = (&mut left[first], &mut right[0]);
+            a.reset();
+            b.reset();
+            let mut history_a: Vec<Round> = Vec::with_capacity(rounds);
+            let mut history_b: Vec<Round> = Vec::with_capacity(rounds);
+            for _ in 0..rounds {
+                let throw_a = a.throw(game, &history_a);
+                let throw_b = b.throw(game, &history_b);
+                history_a.push(Round { you: throw_a, opponent: throw_b });
+                history_b.push(Round { you: throw_b, opponent: throw_a });
+            }
+            for (player, history) in [(first, &history_a), (second, &history_b)] {
+                for &round in history {
+                    standings[player].add(game, round);
+                }
+            }
+        }
+    }
+    standings.sort_by_key(|standing| Reverse(standing.points));
+    Ok(Leaderboard(standings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(a: impl Strategy + 'static, b: impl Strategy + 'static, rounds: usize) -> Leaderboard {
+        let mut players: Vec<Box<dyn Strategy>> = vec![Box::new(a), Box::new(b)];
+        round_robin(&Game::classic(), &mut players, rounds).unwrap()
+    }
+
+    #[test]
+    fn test_matches() {
+        // Copying rock is rock.
+        let board = play(AlwaysRock, CopyLast, 10);
+        assert!(board.0.iter().all(|standing| standing.tally.ties == 10 && standing.points == 40));
+
+        // Rock, paper, scissors against rock: a tie, a win and a loss.
+        let board = play(AlwaysRock, Cycle, 3);
+        assert_eq!(board.0[0], Standing { name: "cycle".into(), points: 15, tally: Tally { wins: 1, ties: 1, losses: 1 } });
+        assert_eq!(board.0[1], Standing { name: "always rock".into(), points: 12, tally: Tally { wins: 1, ties: 1, losses: 1 } });
+
+        // Once it has seen rock, the frequency counter plays paper.
+        let board = play(AlwaysRock, FrequencyCounter, 5);
+        assert_eq!(board.0[0], Standing { name: "frequency counter".into(), points: 36, tally: Tally { wins: 4, ties: 1, losses: 0 } });
+    }
+
+    #[test]
+    fn test_random_is_reproducible() {
+        let game = Game::classic();
+        let mut random = Random::new(7);
+        let throws: Vec<usize> = (0..20).map(|_| random.throw(&game, &[])).collect();
+        assert!(throws.iter().all(|&throw| throw < 3));
+        random.reset();
+        assert_eq!((0..20).map(|_| random.throw(&game, &[])).collect::<Vec<_>>(), throws);
+        assert_eq!(play(Random::new(7), Cycle, 50), play(Random::new(7), Cycle, 50));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut players = bots(1);
+        let board = round_robin(&Game::classic(), &mut players, 100).unwrap();
+        assert_eq!(board.0.len(), 5);
+        assert!(board.0.windows(2).all(|pair| pair[0].points >= pair[1].points));
+        // Every player plays the other four, and every round someone wins is someone's loss.
+        for standing in &board.0 {
+            let tally = standing.tally;
+            assert_eq!(tally.wins + tally.ties + tally.losses, 400);
+        }
+        let wins: usize = board.0.iter().map(|standing| standing.tally.wins).sum();
+        let losses: usize = board.0.iter().map(|standing| standing.tally.losses).sum();
+        assert_eq!(wins, losses);
+        // The bots play any game, not just the classic one.
+        let board = round_robin(&Game::rpsls(), &mut players, 10).unwrap();
+        assert_eq!(board.0.len(), 5);
+    }
+
+    #[test]
+    fn test_too_many_rounds() {
+        // Four opponents at up to 9 points a round: 59652323 rounds fit in an i32, one more
+        // might not.
+        let mut players = bots(1);
+        assert_eq!(
+            round_robin(&Game::classic(), &mut players, 59652324),
+            Err(AocError::solve("Too many rounds to keep score: 59652324"))
+        );
+        assert!(round_robin(&Game::classic(), &mut players, usize::MAX).is_err());
+    }
+}
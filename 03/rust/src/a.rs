@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer};
+
+use crate::rucksack::score_letter;
+
+pub fn main(rucksacks: &[String]) -> AocResult<Answer> {
+    let mut total_score = 0;
+    // Split each line into two parts.
+    for line in rucksacks {
+        let halfway_point = line.len() / 2;
+        let (first_half, second_half) = line.split_at(halfway_point);
+        // Build a hash set from the letters in the first half.
+        let first_rucksack: HashSet<char> = first_half.chars().collect();
+        let second_rucksack: HashSet<char> = second_half.chars().collect();
+        // Find the elements in common
+        let common: HashSet<_> = first_rucksack.intersection(&second_rucksack).collect();
+        if common.len() != 1 {
+            return Err(AocError::solve("There can only be one common element!"));
+        };
+        let common_element = match common.iter().next() {
+            Some(common_element) => common_element,
+            None => return Err(AocError::solve("There must be one common element!")),
+        };
+        let current_score = score_letter(common_element)?;
+        // Add the current score to the total score.
+        total_score += current_score;
+    }
+
+    Ok(Answer::from(total_score))
+}
@@ -0,0 +1,43 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer};
+
+use crate::rucksack::score_letter;
+
+pub fn main(rucksacks: &[String]) -> AocResult<Answer> {
+    let lines: Vec<&str> = rucksacks.iter().map(|s| s.as_str()).collect();
+    if !lines.len().is_multiple_of(3) {
+        return Err(AocError::solve("The number of rucksacks must be a multiple of three"));
+    }
+    // Split the lines into groups of three.
+    let groups: Vec<&[&str]> = lines.chunks(3).collect();
+    let mut total_score = 0;
+    for group in groups {
+        let group = group.to_vec();
+        let group_score = score_group(group)?;
+        total_score += group_score;
+    }
+    Ok(Answer::from(total_score))
+}
+
+fn score_group(group: Vec<&str>) -> AocResult<i32> {
+    let rucksacks: Vec<HashSet<char>> = group
+        .iter()
+        .map(|s| s.chars().collect())
+        .collect();
+
+    let common = rucksacks
+        .iter()
+        .fold(rucksacks[0].clone(), |acc, x| {
+            acc.intersection(x).cloned().collect()
+        }
+    );
+    if common.len() != 1 {
+        return Err(AocError::solve("There can only be one common element!"));
+    }
+    let common_letter = match common.iter().next() {
+        Some(common_letter) => common_letter,
+        None => return Err(AocError::solve("There must be one common element!")),
+    };
+    score_letter(common_letter)
+}
@@ -0,0 +1,36 @@
+use common::{Generator, Rng};
+
+use crate::Day03;
+
+/// `size` groups of three rucksacks. Each rucksack's compartments share exactly one item
+/// type, and each group shares exactly one badge.
+impl Generator for Day03 {
+    const DEFAULT_SIZE: usize = 100;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        let mut text = String::new();
+        for _ in 0..size {
+            let mut items = items.clone();
+            rng.shuffle(&mut items);
+            let badge = items[0];
+            // Each elf packs from their own 17 item types, so only the badge is common.
+            for pool in items[1..].chunks(17) {
+                let (shared, pool) = (pool[0], &pool[1..]);
+                let (first_pool, second_pool) = pool.split_at(pool.len() / 2);
+                let length = rng.range(2..=16) as usize;
+                let mut compartments = [vec![shared], vec![shared]];
+                compartments[rng.below(2)].push(badge);
+                for (compartment, pool) in compartments.iter_mut().zip([first_pool, second_pool]) {
+                    while compartment.len() < length {
+                        compartment.push(*rng.choose(pool));
+                    }
+                    rng.shuffle(compartment);
+                    text.extend(compartment.iter());
+                }
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
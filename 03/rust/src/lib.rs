@@ -0,0 +1,29 @@
+mod a;
+mod b;
+mod generate;
+mod reference;
+mod rucksack;
+
+use common::{AocResult, Answer, Part, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<String>;
+
+    fn parse(contents: &str) -> AocResult<Self::Input> {
+        Ok(contents.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part_a(input: &Self::Input) -> AocResult<Answer> {
+        a::main(input)
+    }
+
+    fn part_b(input: &Self::Input) -> AocResult<Answer> {
+        b::main(input)
+    }
+
+    fn reference(contents: &str, part: Part) -> AocResult<Answer> {
+        reference::solve(contents, part)
+    }
+}
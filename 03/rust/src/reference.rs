@@ -0,0 +1,32 @@
+use common::{AocError, AocResult, Answer, Part};
+
+fn priority(item: char) -> usize {
+    ('a'..='z').chain('A'..='Z').position(|c| c == item).unwrap() + 1
+}
+
+/// The first item in `items` that's also in each of `others`.
+fn common_item(items: &str, others: &[&str]) -> AocResult<char> {
+    items
+        .chars()
+        .find(|c| others.iter().all(|other| other.contains(*c)))
+        .ok_or_else(|| AocError::parse(items, "No item in common"))
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut total = 0;
+    match part {
+        Part::A => {
+            for line in lines {
+                let (first, second) = line.split_at(line.len() / 2);
+                total += priority(common_item(first, &[second])?);
+            }
+        }
+        Part::B => {
+            for group in lines.chunks(3) {
+                total += priority(common_item(group[0], &group[1..])?);
+            }
+        }
+    }
+    Ok(Answer::from(total))
+}
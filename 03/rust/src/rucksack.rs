@@ -0,0 +1,28 @@
+use common::{AocError, AocResult};
+
+pub fn score_letter(c: &char) -> AocResult<i32> {
+    // Get the ascii integer value of the character.
+    let raw_value = *c as i32;
+
+    if *c >= 'a' && *c <= 'z' {
+        Ok(raw_value - 96)
+    } else if *c >= 'A' && *c <= 'Z' {
+        Ok(raw_value - 64 + 26)
+    } else {
+        Err(AocError::solve(&format!("Invalid item {:?}", c)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_letter() {
+        assert_eq!(score_letter(&'a'), Ok(1));
+        assert_eq!(score_letter(&'z'), Ok(26));
+        assert_eq!(score_letter(&'A'), Ok(27));
+        assert_eq!(score_letter(&'Z'), Ok(52));
+        assert!(score_letter(&'1').is_err());
+    }
+}
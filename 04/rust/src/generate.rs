@@ -0,0 +1,21 @@
+use common::{Generator, Rng};
+
+use crate::Day04;
+
+/// `size` pairs of section assignments.
+impl Generator for Day04 {
+    const DEFAULT_SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut range = || {
+            let start = rng.range(1..=99);
+            (start, rng.range(start..=99))
+        };
+        (0..size)
+            .map(|_| {
+                let ((a, b), (c, d)) = (range(), range());
+                format!("{}-{},{}-{}\n", a, b, c, d)
+            })
+            .collect()
+    }
+}
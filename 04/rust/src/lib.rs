@@ -0,0 +1,30 @@
+mod a;
+mod b;
+mod generate;
+mod reference;
+mod range;
+
+use common::{parse_lines, AocResult, Answer, Part, Solution};
+use range::Range;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<(Range, Range)>;
+
+    fn parse(contents: &str) -> AocResult<Self::Input> {
+        parse_lines(contents, Range::from_line)
+    }
+
+    fn part_a(input: &Self::Input) -> AocResult<Answer> {
+        a::main(input)
+    }
+
+    fn part_b(input: &Self::Input) -> AocResult<Answer> {
+        b::main(input)
+    }
+
+    fn reference(contents: &str, part: Part) -> AocResult<Answer> {
+        reference::solve(contents, part)
+    }
+}
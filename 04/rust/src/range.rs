@@ -1,13 +1,15 @@
+use common::{AocError, AocResult};
+
 pub struct Range {
     start: i32,
     end: i32,
 }
 
 impl Range {
-    pub fn from_line(line: &str) -> Result<(Self, Self), String> {
+    pub fn from_line(line: &str) -> AocResult<(Self, Self)> {
         let parts: Vec<&str> = line.split(",").collect();
         if parts.len() != 2 {
-            panic!("Need two ranges per line");
+            return Err(AocError::parse("", "Need two ranges per line"));
         }
         Ok((
             Range::from_string(parts[0])?,
@@ -15,24 +17,18 @@ impl Range {
         ))
     }
 
-    pub fn from_string(s: &str) -> Result<Self, String> {
+    pub fn from_string(s: &str) -> AocResult<Self> {
         let parts: Vec<&str> = s.split("-").collect();
         if parts.len() != 2 {
-            return Err(String::from("Need two numbers per range"));
+            return Err(AocError::parse(s, "Need two numbers per range"));
         }
-        let parsed_parts: Vec<Result<i32, _>> = parts
-            .into_iter()
-            .map( |s| s.parse::<i32>())
-            .collect();
-        let start = match &parsed_parts[0] {
-            Ok(n) => n,
-            Err(e) => return Err(format!("Error parsing start: {}", e)),
-        };
-        let end = match &parsed_parts[1] {
-            Ok(n) => n,
-            Err(e) => return Err(format!("Error parsing end: {}", e)),
-        };
-        Ok(Range{ start: *start, end: *end })
+        let start = parts[0]
+            .parse::<i32>()
+            .map_err(|e| AocError::parse(parts[0], &format!("Error parsing start: {}", e)))?;
+        let end = parts[1]
+            .parse::<i32>()
+            .map_err(|e| AocError::parse(parts[1], &format!("Error parsing end: {}", e)))?;
+        Ok(Range{ start, end })
     }
 
     pub fn contains(&self, other: &Self) -> bool {
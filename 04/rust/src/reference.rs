@@ -0,0 +1,29 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer, Part};
+
+/// The sections in an assignment like `2-4`.
+fn sections(range: &str) -> AocResult<HashSet<u32>> {
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| AocError::parse(range, "Expected a range"))?;
+    Ok((start.parse::<u32>()?..=end.parse::<u32>()?).collect())
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let mut count = 0;
+    for line in contents.lines() {
+        let (first, second) = line
+            .split_once(',')
+            .ok_or_else(|| AocError::parse(line, "Expected two ranges"))?;
+        let (first, second) = (sections(first)?, sections(second)?);
+        let counts = match part {
+            Part::A => first.is_subset(&second) || second.is_subset(&first),
+            Part::B => !first.is_disjoint(&second),
+        };
+        if counts {
+            count += 1;
+        }
+    }
+    Ok(Answer::from(count))
+}
@@ -1,18 +1,24 @@
-use crate::cargo;
+use common::{AocError, AocResult, Answer};
 
-pub fn main(input: String) -> Result<String, String> {
-    let state = cargo::parse_input(input)?;
-    let mut stacks = state.stacks;
-    let instructions = state.instructions;
+use crate::cargo::GameState;
 
-    for instr in instructions {
+pub fn main(state: &GameState) -> AocResult<Answer> {
+    let mut stacks = state.stacks.clone();
+
+    for instr in &state.instructions {
         // Execute each instruction N times.
         for _ in 0..instr.count {
-            let crate_ = stacks[instr.from as usize].pop().unwrap();
+            let crate_ = stacks[instr.from as usize]
+                .pop()
+                .ok_or(AocError::solve("Not enough crates"))?;
             stacks[instr.to as usize].push(crate_);
         }
     }
 
-    let s: String = stacks.iter().map(|s| s.peek().unwrap().char).collect();
-    Ok(s)
-}
\ No newline at end of file
+    let s: String = stacks
+        .iter()
+        .map(|s| s.peek().map(|c| c.char))
+        .collect::<Option<String>>()
+        .ok_or(AocError::solve("A stack ended up empty"))?;
+    Ok(Answer::from(s))
+}
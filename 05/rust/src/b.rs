@@ -1,20 +1,23 @@
-use crate::cargo;
+use common::{AocError, AocResult, Answer};
 
+use crate::cargo::GameState;
 
-pub fn main(input: String) -> Result<String, String> {
-    let state = cargo::parse_input(input)?;
-    let mut stacks = state.stacks;
-    let instructions = state.instructions;
+pub fn main(state: &GameState) -> AocResult<Answer> {
+    let mut stacks = state.stacks.clone();
 
-    for instr in instructions {
+    for instr in &state.instructions {
         let crates = stacks[instr.from as usize]
             .popn(instr.count as usize)
-            .ok_or("Not enough crates")?;
+            .ok_or(AocError::solve("Not enough crates"))?;
         for c in crates {
             stacks[instr.to as usize].push(c);
         }
     }
 
-    let s: String = stacks.iter().map(|s| s.peek().unwrap().char).collect();
-    Ok(s)
-}
\ No newline at end of file
+    let s: String = stacks
+        .iter()
+        .map(|s| s.peek().map(|c| c.char))
+        .collect::<Option<String>>()
+        .ok_or(AocError::solve("A stack ended up empty"))?;
+    Ok(Answer::from(s))
+}
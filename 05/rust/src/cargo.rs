@@ -1,4 +1,5 @@
 use std::fmt;
+use common::{parse_lines, AocError, AocResult};
 use regex::Regex;
 
 #[derive(Clone)]
@@ -18,6 +19,7 @@ impl fmt::Display for Crate {
     }
 }
 
+#[derive(Clone)]
 pub struct Stack {
     crates: Vec<Crate>,
 }
@@ -36,7 +38,7 @@ impl Stack {
     }
 
     pub fn popn(&mut self, n: usize) -> Option<Vec<Crate>> {
-        let start = self.len() - n;
+        let start = self.len().checked_sub(n)?;
         let end = self.len();
         let crates = self.crates[start..end].to_vec();
         self.crates = self.crates[0..start].to_vec();
@@ -70,16 +72,28 @@ pub struct Instruction {
 }
 
 impl Instruction {
-    pub fn parse(line: &str) -> Self {
+    pub fn parse(line: &str, stack_count: usize) -> AocResult<Self> {
         // instructions come in the form "move 1 from 1 to 2".
         let line = line.trim();
-        let re = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-        let captures = re.captures(line).unwrap();
-        Instruction {
-            count: captures[1].parse::<u32>().unwrap(),
-            from: captures[2].parse::<u32>().unwrap() - 1,
-            to: captures[3].parse::<u32>().unwrap() - 1,
-        }
+        let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+        let captures = re
+            .captures(line)
+            .ok_or(AocError::parse("", "Expected \"move <n> from <stack> to <stack>\""))?;
+        let stack = |i: usize| -> AocResult<u32> {
+            let text = &captures[i];
+            match text.parse::<u32>() {
+                Ok(n) if n >= 1 && n as usize <= stack_count => Ok(n - 1),
+                _ => {
+                    let column = captures.get(i).unwrap().start() + 1;
+                    Err(AocError::parse(text, "No such stack").at(0, column))
+                }
+            }
+        };
+        Ok(Instruction {
+            count: captures[1].parse::<u32>()?,
+            from: stack(2)?,
+            to: stack(3)?,
+        })
     }
 }
 
@@ -88,30 +102,43 @@ pub struct GameState {
     pub instructions: Vec<Instruction>,
 }
 
-pub fn parse_input(input: String) -> Result<GameState, String> {
+pub fn parse_input(input: &str) -> AocResult<GameState> {
     // Break the input into two chunks based on the blank line.
-    let chunks: Vec<&str> = input.split("\n\n").collect();
+    let (stack_chunk, instruction_chunk) = input
+        .split_once("\n\n")
+        .ok_or(AocError::parse("", "Expected a blank line after the stacks"))?;
 
-    let stacks = parse_stacks(chunks[0])?;
-    let instructions = parse_instructions(chunks[1])?;
+    let stacks = parse_stacks(stack_chunk)?;
+    // The instructions start after the stack lines and the blank line.
+    let instructions = parse_instructions(instruction_chunk, stacks.len())
+        .map_err(|e| e.offset_lines(stack_chunk.lines().count() + 1))?;
 
     Ok(GameState { stacks, instructions })
 }
 
-fn parse_stacks(input: &str) -> Result<Vec<Stack>, String> {
+fn parse_stacks(input: &str) -> AocResult<Vec<Stack>> {
     let lines: Vec<&str> = input.lines().collect::<Vec<&str>>();
     let n_lines: usize = lines.len();
+    if n_lines == 0 {
+        return Err(AocError::parse("", "No stacks").at(1, 1));
+    }
 
     let number_line: &str = lines[n_lines - 1];
 
     let crate_lines = &lines[0..n_lines - 1];
 
-    let column_count: usize = number_line
-        .split_whitespace()
-        .last()
-        .ok_or(String::from("No number"))?
-        .parse::<usize>()
-        .expect("Not a number");
+    // The stacks are numbered 1, 2, 3... in order, so there are as many as there are numbers.
+    let labels: Vec<&str> = number_line.split_whitespace().collect();
+    if labels.is_empty() {
+        return Err(AocError::parse("", "No stack numbers").on_line(n_lines, number_line));
+    }
+    for (i, &label) in labels.iter().enumerate() {
+        if label.parse::<usize>() != Ok(i + 1) {
+            let message = format!("Expected stack number {}", i + 1);
+            return Err(AocError::parse(label, &message).on_line(n_lines, number_line));
+        }
+    }
+    let column_count = labels.len();
 
     let mut stacks: Vec<Stack> = Vec::new();
     for _ in 0..column_count {
@@ -121,22 +148,42 @@ fn parse_stacks(input: &str) -> Result<Vec<Stack>, String> {
     // Start at the bottom of each column and add crates to the stacks.
     for line in crate_lines.iter().rev() {
         let chars: Vec<char> = line.chars().collect::<Vec<char>>();
-        for i in 0..column_count {
+        for (i, stack) in stacks.iter_mut().enumerate() {
             // Figure out where in the line we should look for this column's crate.
             let line_pos: usize = 1 + i * 4; 
             let char = chars.get(line_pos).unwrap_or(&' ');
 
             if *char == ' ' { continue };
-            stacks[i].push(Crate::new(*char));
+            stack.push(Crate::new(*char));
         }
     }
     Ok(stacks)
 }
 
-fn parse_instructions(input: &str) -> Result<Vec<Instruction>, String> {
-    let instructions: Vec<Instruction> = input
-        .lines()
-        .map(|line: &str| Instruction::parse(line))
-        .collect();
-    Ok(instructions)
+fn parse_instructions(input: &str, stack_count: usize) -> AocResult<Vec<Instruction>> {
+    parse_lines(input, |line| Instruction::parse(line, stack_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input_errors() {
+        let stacks = "    [D]\n[N] [C]\n 1   2 \n\n";
+        assert!(parse_input(&format!("{}move 1 from 2 to 1", stacks)).is_ok());
+        assert_eq!(
+            parse_input(&format!("{}move 1 from 2 to 1\nmove 1 from 3 to 1", stacks)).err(),
+            Some(AocError::parse("3", "No such stack").at(6, 13))
+        );
+        assert_eq!(
+            parse_input(&format!("{}jump 1", stacks)).err(),
+            Some(AocError::parse("jump 1", "Expected \"move <n> from <stack> to <stack>\"").at(5, 1))
+        );
+        assert!(parse_input("[A]\n 1 ").is_err());
+        assert_eq!(
+            parse_input("[A] [B]\n 1   4294967295 \n\nmove 1 from 1 to 2").err(),
+            Some(AocError::parse("4294967295", "Expected stack number 2").at(2, 6))
+        );
+    }
 }
\ No newline at end of file
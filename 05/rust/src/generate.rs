@@ -0,0 +1,49 @@
+use common::{Generator, Rng};
+
+use crate::Day05;
+
+/// A drawing of up to nine stacks, followed by `size` moves. Moves never empty a stack, so
+/// every stack has a crate on top at the end.
+impl Generator for Day05 {
+    const DEFAULT_SIZE: usize = 500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let letters: Vec<char> = ('A'..='Z').collect();
+        let mut stacks: Vec<Vec<char>> = (0..rng.range(2..=9))
+            .map(|_| (0..rng.range(1..=8)).map(|_| *rng.choose(&letters)).collect())
+            .collect();
+
+        let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+        let mut text = String::new();
+        for level in (0..height).rev() {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => String::from("   "),
+                })
+                .collect();
+            text.push_str(&row.join(" "));
+            text.push('\n');
+        }
+        let numbers: Vec<String> = (1..=stacks.len()).map(|i| format!(" {} ", i)).collect();
+        text.push_str(&numbers.join(" "));
+        text.push_str("\n\n");
+
+        let mut moves = 0;
+        while moves < size {
+            let from = rng.below(stacks.len());
+            let to = rng.below(stacks.len());
+            if from == to || stacks[from].len() < 2 {
+                continue;
+            }
+            let count = rng.range(1..=stacks[from].len() as i64 - 1) as usize;
+            let start = stacks[from].len() - count;
+            let moved: Vec<char> = stacks[from].drain(start..).collect();
+            stacks[to].extend(moved);
+            text.push_str(&format!("move {} from {} to {}\n", count, from + 1, to + 1));
+            moves += 1;
+        }
+        text
+    }
+}
@@ -0,0 +1,30 @@
+mod a;
+mod b;
+mod generate;
+mod reference;
+mod cargo;
+
+use common::{AocResult, Answer, Part, Solution};
+use cargo::GameState;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = GameState;
+
+    fn parse(contents: &str) -> AocResult<Self::Input> {
+        cargo::parse_input(contents)
+    }
+
+    fn part_a(input: &Self::Input) -> AocResult<Answer> {
+        a::main(input)
+    }
+
+    fn part_b(input: &Self::Input) -> AocResult<Answer> {
+        b::main(input)
+    }
+
+    fn reference(contents: &str, part: Part) -> AocResult<Answer> {
+        reference::solve(contents, part)
+    }
+}
@@ -0,0 +1,43 @@
+use common::{AocError, AocResult, Answer, Part};
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let (drawing, moves) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| AocError::parse("", "Expected a blank line after the stacks"))?;
+    let mut rows: Vec<&str> = drawing.lines().collect();
+    let numbers = rows.pop().unwrap_or_default();
+    let mut stacks: Vec<Vec<char>> = vec![Vec::new(); numbers.split_whitespace().count()];
+    // Crate letters sit at columns 1, 5, 9...; read the rows bottom up.
+    for row in rows.iter().rev() {
+        for (i, c) in row.chars().enumerate() {
+            if c.is_ascii_uppercase() {
+                stacks[i / 4].push(c);
+            }
+        }
+    }
+
+    for line in moves.lines() {
+        let words: Vec<&str> = line.split(' ').collect();
+        let [_, count, _, from, _, to] = words[..] else {
+            return Err(AocError::parse(line, "Expected a move"));
+        };
+        let (count, from, to) = (count.parse::<usize>()?, from.parse::<usize>()? - 1, to.parse::<usize>()? - 1);
+        match part {
+            // One crate at a time...
+            Part::A => {
+                for _ in 0..count {
+                    let c = stacks[from].pop().ok_or(AocError::NoAnswer)?;
+                    stacks[to].push(c);
+                }
+            }
+            // ...or all of them at once.
+            Part::B => {
+                let start = stacks[from].len().checked_sub(count).ok_or(AocError::NoAnswer)?;
+                let moved: Vec<char> = stacks[from].drain(start..).collect();
+                stacks[to].extend(moved);
+            }
+        }
+    }
+    let tops: String = stacks.iter().filter_map(|stack| stack.last()).collect();
+    Ok(Answer::from(tops))
+}
@@ -1,18 +1,20 @@
+use common::{AocError, AocResult, Answer};
+
 const WINDOW_LENGTH: usize = 14;
 
-pub fn main(contents: String) -> Result<String, String> {
+pub fn main(contents: &str) -> AocResult<Answer> {
     for window_end in WINDOW_LENGTH..contents.len() {
         let window_start = window_end - WINDOW_LENGTH;
         let window = &contents[window_start..window_end];
         if all_chars_unique(window) {
             // We use one-based indexing in this madhouse, but ranges like [0, 4] are
             // exclusive on the right, which cancels out the need to add one.
-            return Ok(window_end.to_string());
+            return Ok(Answer::from(window_end));
         }
     }
     
 
-    Err(String::from("No answer found"))
+    Err(AocError::NoAnswer)
 }
 
 fn all_chars_unique(s: &str) -> bool {
@@ -45,7 +47,7 @@ mod tests {
             ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", "26"),
         ];
         for (content, answer) in contents_and_answers {
-            let actual_answer = main(content.to_string()).unwrap();
+            let actual_answer = main(content).unwrap().to_string();
             assert_eq!(actual_answer, answer, "Failed on {}", content);
         }
     }
@@ -0,0 +1,24 @@
+use common::{Generator, Rng};
+
+use crate::Day06;
+
+const MESSAGE_MARKER: usize = 14;
+
+/// A datastream of `size` characters (at least 14). Most of it repeats a few letters, with
+/// one run of 14 different letters somewhere, so both markers are always found.
+impl Generator for Day06 {
+    const DEFAULT_SIZE: usize = 4096;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(MESSAGE_MARKER);
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut letters);
+        let (marker, common) = letters.split_at(MESSAGE_MARKER);
+        let mut stream: Vec<char> = (0..size - MESSAGE_MARKER)
+            .map(|_| *rng.choose(&common[..4]))
+            .collect();
+        let at = rng.below(stream.len() + 1);
+        stream.splice(at..at, marker.iter().copied());
+        stream.into_iter().chain(['\n']).collect()
+    }
+}
@@ -0,0 +1,16 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer, Part};
+
+/// How many characters have been read when the last `length` were all different.
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let length = match part {
+        Part::A => 4,
+        Part::B => 14,
+    };
+    let chars: Vec<char> = contents.trim().chars().collect();
+    (length..=chars.len())
+        .find(|end| chars[end - length..*end].iter().collect::<HashSet<_>>().len() == length)
+        .map(Answer::from)
+        .ok_or(AocError::NoAnswer)
+}
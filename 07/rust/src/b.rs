@@ -1,13 +1,11 @@
+use common::{AocError, AocResult, Answer};
+
 use crate::node::{Node, NodeData};
-use crate::parse;
 
 const TOTAL_SPACE: i32 = 70000000;
 const NEEDED_SPACE: i32 = 30000000;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let commands = parse::parse(contents)?;
-    let tree = Node::new_from_commands(commands)?;
-
+pub fn main(tree: &Node) -> AocResult<Answer> {
     let used_space = tree.size();
     let free_space = TOTAL_SPACE - used_space;
     let deficit = NEEDED_SPACE - free_space;
@@ -16,16 +14,16 @@ pub fn main(contents: String) -> Result<String, String> {
     let descendants = tree.descendants();
     let big_dirs: Vec<(&String, i32)> = descendants
         .iter()
-        .filter(|x| match x.value {
-            NodeData::Directory => true,
-            _ => false,
-        })
+        .filter(|x| matches!(x.value, NodeData::Directory))
         .filter(|x| x.size() >= deficit)
         .map(|x| (&x.name, x.size()))
         .collect();
 
     // Find the smallest of these
-    let smallest_big_dir = big_dirs.iter().min_by_key(|(_, size)| size).unwrap();
+    let smallest_big_dir = big_dirs
+        .iter()
+        .min_by_key(|(_, size)| size)
+        .ok_or(AocError::NoAnswer)?;
 
-    Ok(smallest_big_dir.1.to_string())
+    Ok(Answer::from(smallest_big_dir.1))
 }
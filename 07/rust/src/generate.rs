@@ -0,0 +1,89 @@
+use common::{Generator, Rng};
+
+use crate::Day07;
+
+const EXTENSIONS: [&str; 5] = ["", ".txt", ".dat", ".log", ".lst"];
+
+struct Directory {
+    name: String,
+    files: Vec<(String, i64)>,
+    children: Vec<usize>,
+}
+
+/// A `$ cd`/`$ ls` transcript exploring `size` directories (at least 2). The files add up
+/// to between 45 and 65 million, topped up by one big file below the root, so there's
+/// always a directory to delete for the update.
+impl Generator for Day07 {
+    const DEFAULT_SIZE: usize = 200;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut directories = vec![Directory {
+            name: String::from("/"),
+            files: Vec::new(),
+            children: Vec::new(),
+        }];
+        for i in 1..size.max(2) {
+            let parent = rng.below(i);
+            let name = unique_name(rng, &directories, parent, "");
+            directories[parent].children.push(i);
+            directories.push(Directory { name, files: Vec::new(), children: Vec::new() });
+        }
+        for i in 0..directories.len() {
+            for _ in 0..rng.range(0..=5) {
+                let extension = *rng.choose(&EXTENSIONS);
+                let name = unique_name(rng, &directories, i, extension);
+                let size = rng.range(1000..=300_000);
+                directories[i].files.push((name, size));
+            }
+        }
+
+        let total: i64 = directories.iter().flat_map(|d| &d.files).map(|(_, size)| size).sum();
+        let target = rng.range(45_000_000..=65_000_000);
+        if total > target {
+            for (_, size) in directories.iter_mut().flat_map(|d| &mut d.files) {
+                *size = (*size * target / total).max(1);
+            }
+        }
+        let total: i64 = directories.iter().flat_map(|d| &d.files).map(|(_, size)| size).sum();
+        if total < target {
+            let name = unique_name(rng, &directories, 1, ".bin");
+            directories[1].files.push((name, target - total));
+        }
+
+        let mut text = String::from("$ cd /\n");
+        transcribe(&directories, 0, &mut text);
+        text
+    }
+}
+
+/// List a directory, then visit each of its subdirectories and come back up.
+fn transcribe(directories: &[Directory], i: usize, text: &mut String) {
+    let directory = &directories[i];
+    text.push_str("$ ls\n");
+    for child in &directory.children {
+        text.push_str(&format!("dir {}\n", directories[*child].name));
+    }
+    for (name, size) in &directory.files {
+        text.push_str(&format!("{} {}\n", size, name));
+    }
+    for child in &directory.children {
+        text.push_str(&format!("$ cd {}\n", directories[*child].name));
+        transcribe(directories, *child, text);
+        text.push_str("$ cd ..\n");
+    }
+}
+
+/// A short name that isn't taken in the directory yet.
+fn unique_name(rng: &mut Rng, directories: &[Directory], parent: usize, extension: &str) -> String {
+    let directory = &directories[parent];
+    loop {
+        let length = rng.range(1..=8);
+        let stem: String = (0..length).map(|_| (b'a' + rng.below(26) as u8) as char).collect();
+        let name = stem + extension;
+        let taken = directory.children.iter().any(|child| directories[*child].name == name)
+            || directory.files.iter().any(|(file, _)| *file == name);
+        if !taken {
+            return name;
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::parse::{Command, Listing};
+use common::{AocError, AocResult};
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
@@ -17,7 +18,7 @@ pub struct Node {
 }
 
 impl Node {
-    pub fn new_from_commands(commands: Vec<Command>) -> Result<Node, String> {
+    pub fn new_from_commands(commands: Vec<Command>) -> AocResult<Node> {
         let root = Node::new_root();
         let root_ref = Rc::new(root);
         let mut current_dir = Rc::clone(&root_ref);
@@ -49,22 +50,26 @@ impl Node {
                                 current_dir = parent;
                                 continue;
                             } else {
-                                panic!("Can't navigate above a root directory");
+                                return Err(AocError::parse(
+                                    "$ cd ..",
+                                    "Can't navigate above a root directory",
+                                ));
                             };
                         }
                         _ => {
                             // Find the directory
                             current_dir = current_dir
                                 .find_child(&directory)
-                                .ok_or_else(|| String::from("Directory not found"))?;
+                                .ok_or_else(|| AocError::parse(&directory, "Directory not found"))?;
                         }
                     }
                 }
             }
         }
 
-        // Get the underlying value out of root_ref
-        let root = Rc::try_unwrap(root_ref).map_err(|_| String::from("Could not unwrap root"))?;
+        // Get the underlying value out of root_ref, which the transcript may have ended in.
+        drop(current_dir);
+        let root = Rc::try_unwrap(root_ref).map_err(|_| AocError::solve("Could not unwrap root"))?;
         Ok(root)
     }
 
@@ -129,3 +134,16 @@ impl Node {
         descendants
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_transcript_ending_at_root() {
+        let commands = parse::parse("$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n10 b\n$ cd ..").unwrap();
+        let root = Node::new_from_commands(commands).unwrap();
+        assert_eq!(root.size(), 10);
+    }
+}
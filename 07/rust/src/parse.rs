@@ -1,3 +1,5 @@
+use common::{AocError, AocResult};
+
 # [derive(Debug)]
 enum ParsedLine {
     CommandLine(String),
@@ -6,8 +8,7 @@ enum ParsedLine {
 
 impl ParsedLine {
     fn from_string(s: &str) -> ParsedLine {
-        if s.starts_with("$ ") {
-            let command = &s[2..];
+        if let Some(command) = s.strip_prefix("$ ") {
             return ParsedLine::CommandLine(String::from(command))
         }
         ParsedLine::OutputLine(String::from(s))
@@ -27,62 +28,84 @@ pub enum Listing {
 }
 
 impl Listing {
-    fn from_string(s: &str) -> Result<Listing, String> {
+    fn from_string(s: &str) -> AocResult<Listing> {
         let parts = s.split(" ").collect::<Vec<&str>>();
         if parts.len() != 2 {
-            return Err(String::from("Invalid listing; more than two parts"));
+            return Err(AocError::parse("", "Invalid listing; expected two parts"));
         }
         match parts[0] {
             "dir" => Ok(Listing::Directory{name: String::from(parts[1])}),
             _ => {
-                let size = parts[0].parse::<i32>().map_err(|_| String::from("Invalid listing; size is not an integer"))?;
+                let size = parts[0].parse::<i32>().map_err(|_| AocError::parse(parts[0], "Invalid listing; size is not an integer"))?;
                 Ok(Listing::File{name: String::from(parts[1]), size})
             },
         }
     }
 }
 
-pub fn parse(contents: String) -> Result<Vec<Command>, String> {
-    let lines = contents.lines();
-    let mut parsed_lines: Vec<ParsedLine> = lines
-        .map(|line| ParsedLine::from_string(line) )
+pub fn parse(contents: &str) -> AocResult<Vec<Command>> {
+    // Keep each line's number and text around so errors can point at it.
+    let mut parsed_lines: Vec<(usize, &str, ParsedLine)> = contents
+        .lines()
+        .enumerate()
+        .map(|(i, s)| (i + 1, s, ParsedLine::from_string(s)))
         .collect();
 
     let mut commands = vec![];
     while !parsed_lines.is_empty() {
-        let line = parsed_lines.remove(0);
+        let (line_number, text, line) = parsed_lines.remove(0);
+        let at_line = |e: AocError| e.on_line(line_number, text);
         if let ParsedLine::CommandLine(command) = line {
             let parts = command.split(" ").collect::<Vec<&str>>();
             let command = match parts[0] {
                 "cd" => {
                     if parts.len() != 2 {
-                        return Err(String::from("cd command must have exactly one argument"));
+                        return Err(at_line(AocError::parse("", "cd command must have exactly one argument")));
                     }
                     Command::Cd{directory: String::from(parts[1])}
                 },
                 "ls" => {
                     if parts.len() != 1 {
-                        return Err(String::from("ls command must have no arguments"));
+                        return Err(at_line(AocError::parse("", "ls command must have no arguments")));
                     }
                     // Once we find a list command, there can be 0+ output commands that follow.
                     let mut output: Vec<Listing> = vec![];
                     // Only consume the line if it's an output line.
-                    while let Some(ParsedLine::OutputLine(_)) = parsed_lines.get(0) {
-                        if let ParsedLine::OutputLine(s) = parsed_lines.remove(0) {
-                            let new_listing = Listing::from_string(&s)?;
+                    while let Some((_, _, ParsedLine::OutputLine(_))) = parsed_lines.first() {
+                        if let (line_number, text, ParsedLine::OutputLine(s)) = parsed_lines.remove(0) {
+                            let new_listing = Listing::from_string(&s)
+                                .map_err(|e| e.on_line(line_number, text))?;
                             output.push(new_listing);
                         }
                     }
                     Command::List{output}
                 },
-                _ => return Err(format!("Unknown command: {}", command)),
+                _ => return Err(at_line(AocError::parse(parts[0], "Unknown command"))),
             };
             commands.push(command);
         } else {
             // If we've gotten here, we somehow found an output line that wasn't after a list command.
-            return Err(String::from("Output line found without a list command"));
+            return Err(at_line(AocError::parse("", "Output line found without a list command")));
         }
     }
     Ok(commands)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("$ cd /\n$ ls\ndir a\n14848514 b.txt").is_ok());
+        assert_eq!(
+            parse("$ cd /\n$ ls\ndir a\nbig b.txt").err(),
+            Some(AocError::parse("big", "Invalid listing; size is not an integer").at(4, 1))
+        );
+        assert_eq!(
+            parse("$ cd /\n$ rm a").err(),
+            Some(AocError::parse("rm", "Unknown command").at(2, 3))
+        );
+    }
+}
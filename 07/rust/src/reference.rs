@@ -0,0 +1,56 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{AocError, AocResult, Answer, Part};
+
+const TOTAL_SPACE: i64 = 70_000_000;
+const NEEDED_SPACE: i64 = 30_000_000;
+
+/// Every directory's size, found by adding up every file whose path starts with it.
+fn directory_sizes(contents: &str) -> AocResult<Vec<i64>> {
+    let mut cwd: Vec<&str> = Vec::new();
+    let mut directories: HashSet<Vec<&str>> = HashSet::from([Vec::new()]);
+    let mut files: HashMap<Vec<&str>, i64> = HashMap::new();
+    for line in contents.lines() {
+        match line.split(' ').collect::<Vec<_>>()[..] {
+            ["$", "cd", "/"] => cwd.clear(),
+            ["$", "cd", ".."] => {
+                cwd.pop();
+            }
+            ["$", "cd", name] => {
+                cwd.push(name);
+                directories.insert(cwd.clone());
+            }
+            ["$", "ls"] => {}
+            ["dir", name] => {
+                directories.insert([&cwd[..], &[name]].concat());
+            }
+            [size, name] => {
+                files.insert([&cwd[..], &[name]].concat(), size.parse::<i64>()?);
+            }
+            _ => return Err(AocError::parse(line, "Unexpected line")),
+        }
+    }
+    Ok(directories
+        .iter()
+        .map(|directory| {
+            files
+                .iter()
+                .filter(|(path, _)| path.starts_with(directory))
+                .map(|(_, size)| size)
+                .sum()
+        })
+        .collect())
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let sizes = directory_sizes(contents)?;
+    match part {
+        Part::A => Ok(Answer::from(sizes.iter().filter(|size| **size <= 100_000).sum::<i64>())),
+        Part::B => {
+            let used = sizes.iter().max().copied().unwrap_or(0);
+            let deficit = NEEDED_SPACE - (TOTAL_SPACE - used);
+            let smallest = sizes.into_iter().filter(|size| *size >= deficit).min();
+            smallest.map(Answer::from).ok_or(AocError::NoAnswer)
+        }
+    }
+}
@@ -82,5 +82,5 @@ for r in range(n_rows):
             best_scenic_score = score
             best_scenic_coords = (r, c)
 
-print(best_scenic_score)
 print(best_scenic_coords)
+print(best_scenic_score)
@@ -1,8 +1,9 @@
+use common::{AocResult, Answer};
+
 use crate::forest::Forest;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let forest = Forest::new_from_text(contents)?;
+pub fn main(forest: &Forest) -> AocResult<Answer> {
     let visible_trees = forest.visible_positions();
     let n_visible = visible_trees.len();
-    Ok(n_visible.to_string())
+    Ok(Answer::from(n_visible))
 }
\ No newline at end of file
@@ -1,7 +1,8 @@
+use common::{AocResult, Answer};
+
 use crate::forest::Forest;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let forest = Forest::new_from_text(contents)?;
+pub fn main(forest: &Forest) -> AocResult<Answer> {
     let (rows, cols) = forest.dimensions();
 
     let mut best_score = -1;
@@ -14,5 +15,5 @@ pub fn main(contents: String) -> Result<String, String> {
         }
     }
 
-    Ok(best_score.to_string())
+    Ok(Answer::from(best_score))
 }
\ No newline at end of file
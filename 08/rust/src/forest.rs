@@ -1,11 +1,12 @@
 use std::fmt;
-use std::collections::HashSet;
+
+use common::{AocError, AocResult, Grid, Point};
 
 // Trees have a height and nothing else.
 pub type Tree = u32;
 
 pub struct Forest {
-    trees: Vec<Vec<Tree>>,
+    trees: Grid<Tree>,
 }
 
 fn take_until_inclusive<T, F>(xs: &Vec<T>, f: F) -> Vec<&T>
@@ -22,70 +23,50 @@ where
     ys
 }
 
-impl Forest {
-    pub fn new_from_text(s: String) -> Result<Self, String> {
-        let trees = s
-        .lines()
-        .map(|line| {
-            line
-            .chars()
-            .map(|c| match c.to_digit(10) {
-                Some(u) => Ok(u),
-                None => Err(format!("invalid character: {}", c)),
-            })
-            .collect::<Result<Vec<Tree>, String>>()
-        }).collect::<Result<Vec<Vec<Tree>>, String>>()?;
+fn point(x: usize, y: usize) -> Point {
+    Point::new(x as i32, y as i32)
+}
 
+impl Forest {
+    pub fn new_from_text(s: String) -> AocResult<Self> {
+        let trees = Grid::parse(&s, "Invalid tree height", |c| c.to_digit(10))?;
         Ok(Forest { trees })
     }
 
-    pub fn at(&self, x: usize, y: usize) -> Result<&Tree, String> {
+    pub fn at(&self, x: usize, y: usize) -> AocResult<&Tree> {
         self.trees
-            .get(y)
-            .and_then(|row| row.get(x))
-            .ok_or_else(|| format!("out of bounds at {}, {}", x, y))
+            .get(point(x, y))
+            .ok_or_else(|| AocError::Solve(format!("out of bounds at {}, {}", x, y)))
     }
 
-    pub fn left_of(&self, x: usize, y: usize) -> Result<Vec<Tree>, String> {
-        // Get all the trees to the right of (x, y).
-        let mut trees = vec![];
-        for x in (0..x).rev() {
-            trees.push(*self.at(x, y)?);
-        } 
-        Ok(trees)
+    // Get all the trees from (x, y) to the edge of the forest, nearest first.
+    fn trees_from(&self, x: usize, y: usize, direction: Point) -> AocResult<Vec<Tree>> {
+        self.at(x, y)?;
+        Ok(self
+            .trees
+            .walk(point(x, y), direction)
+            .map(|p| self.trees[p])
+            .collect())
     }
 
-    pub fn right_of(&self, x: usize, y: usize) -> Result<Vec<Tree>, String> {
-        // Get all the trees to the right of (x, y).
-        let (cols, _) = self.dimensions();
-        let mut trees = vec![];
-        for x in (x + 1)..cols {
-            trees.push(*self.at(x, y)?);
-        } 
-        Ok(trees)
+    pub fn left_of(&self, x: usize, y: usize) -> AocResult<Vec<Tree>> {
+        self.trees_from(x, y, Point::LEFT)
     }
 
-    pub fn above(&self, x: usize, y: usize) -> Result<Vec<Tree>, String> {
-        // Get all the trees above (x, y).
-        let mut trees = vec![];
-        for y in (0..y).rev() {
-            trees.push(*self.at(x, y)?);
-        } 
-        Ok(trees)
+    pub fn right_of(&self, x: usize, y: usize) -> AocResult<Vec<Tree>> {
+        self.trees_from(x, y, Point::RIGHT)
     }
 
-    pub fn below(&self, x: usize, y: usize) -> Result<Vec<Tree>, String> {
-        // Get all the trees below (x, y).
-        let (_, rows) = self.dimensions();
-        let mut trees = vec![];
-        for y in (y + 1)..rows {
-            trees.push(*self.at(x, y)?);
-        } 
-        Ok(trees)
+    pub fn above(&self, x: usize, y: usize) -> AocResult<Vec<Tree>> {
+        self.trees_from(x, y, Point::UP)
     }
 
-    pub fn scenic_score(&self, x: usize, y: usize) -> Result<usize, String> {
-        let current_tree = self.at(x, y)?.clone();
+    pub fn below(&self, x: usize, y: usize) -> AocResult<Vec<Tree>> {
+        self.trees_from(x, y, Point::DOWN)
+    }
+
+    pub fn scenic_score(&self, x: usize, y: usize) -> AocResult<usize> {
+        let current_tree = *self.at(x, y)?;
 
         let trees_left = self.left_of(x, y)?;
         let visible_left = take_until_inclusive(&trees_left, |t| t >= &current_tree);
@@ -106,112 +87,29 @@ impl Forest {
         Ok(left_score * right_score * above_score * below_score)
     }
 
+    /// The number of columns and rows.
     pub fn dimensions(&self) -> (usize, usize) {
-        let width = self.trees.len();
-        let height = self.trees[0].len();
-        (width, height)
-    }
-
-    fn visible_from_top(&self) -> Vec<(usize, usize)> {
-        let (cols, rows) = self.dimensions();
-
-        let mut visible = vec![];
-        for x in 0..cols {
-            // Track the tallest tree we've seen so we know if trees behind it are visible.
-            let mut tallest_seen: i32 = -1;
-            for y in 0..rows {
-                let tree = self.at(x, y).unwrap();
-                if (*tree as i32) > tallest_seen {
-                    visible.push((x,y));
-                    tallest_seen = tree.clone() as i32;
-                }
-            }
-        }
-        visible
-    }
-
-    fn visible_from_bottom(&self) -> Vec<(usize, usize)> {
-        let (cols, rows) = self.dimensions();
-
-        let mut visible = vec![];
-        for x in 0..cols {
-            // Track the tallest tree we've seen so we know if trees behind it are visible.
-            let mut tallest_seen: i32 = -1;
-            for from_bottom in 0..rows {
-                let y = rows - (from_bottom + 1);
-                let tree = self.at(x, y).unwrap();
-                if (*tree as i32) > tallest_seen {
-                    visible.push((x,y));
-                    tallest_seen = tree.clone() as i32;
-                }
-            }
-        }
-        visible
-    }
-
-    fn visible_from_left(&self) -> Vec<(usize, usize)> {
-        let (cols, rows) = self.dimensions();
-
-        let mut visible = vec![];
-        for y in 0..rows {
-            let mut tallest_seen: i32 = -1;
-            for x in 0..cols {
-                let tree = self.at(x, y).unwrap();
-                if (*tree as i32) > tallest_seen {
-                    visible.push((x,y));
-                    tallest_seen = tree.clone() as i32;
-                }
-            }
-        }
-        visible
-    }
-
-    fn visible_from_right(&self) -> Vec<(usize, usize)> {
-        let (cols, rows) = self.dimensions();
-
-        let mut visible = vec![];
-        for y in 0..rows {
-            let mut tallest_seen: i32 = -1;
-            for from_right in 0..cols {
-                let x = cols - (from_right + 1);
-                let tree = self.at(x, y).unwrap();
-                if (*tree as i32) > tallest_seen {
-                    visible.push((x,y));
-                    tallest_seen = tree.clone() as i32;
-                }
-            }
-        }
-        visible
+        (self.trees.width(), self.trees.height())
     }
 
     pub fn visible_positions(&self) -> Vec<(usize, usize)> {
-        // Find all tree positions that are visible from outside the grid.
-
-        let visible_sets = vec![
-            self.visible_from_top(),
-            self.visible_from_bottom(),
-            self.visible_from_left(),
-            self.visible_from_right(),
-        ];
-
-        let mut visible = HashSet::new();
-        visible_sets.iter().for_each(|visible_set| {
-            visible.extend(visible_set);
-        });
-
-        visible.into_iter().collect()
+        // Find all tree positions that are visible from outside the grid, i.e. where every
+        // tree between it and one of the edges is shorter.
+        self.trees
+            .iter()
+            .filter(|(p, tree)| {
+                Point::DIRECTIONS_4
+                    .iter()
+                    .any(|d| self.trees.walk(*p, *d).all(|q| self.trees[q] < **tree))
+            })
+            .map(|(p, _)| (p.x as usize, p.y as usize))
+            .collect()
     }
 }
 
 impl fmt::Display for Forest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in &self.trees {
-            for tree in row {
-                write!(f, "{}", tree)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.trees)
     }
 }
 
@@ -219,15 +117,29 @@ impl fmt::Display for Forest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_new_from_text() {
         let s = String::from("123\n456\n789");
         let forest = Forest::new_from_text(s).unwrap();
-        assert_eq!(forest.trees.len(), 3);
-        assert_eq!(forest.trees[0], vec![1, 2, 3]);
-        assert_eq!(forest.trees[1], vec![4, 5, 6]);
-        assert_eq!(forest.trees[2], vec![7, 8, 9]);
+        assert_eq!(forest.dimensions(), (3, 3));
+        assert_eq!(forest.trees.row(0), &[1, 2, 3]);
+        assert_eq!(forest.trees.row(1), &[4, 5, 6]);
+        assert_eq!(forest.trees.row(2), &[7, 8, 9]);
+    }
+
+    #[test]
+    fn test_new_from_text_errors() {
+        assert_eq!(
+            Forest::new_from_text(String::from("123\n4x6")).err(),
+            Some(AocError::parse("x", "Invalid tree height").at(2, 2))
+        );
+        assert_eq!(
+            Forest::new_from_text(String::from("123\n45")).err(),
+            Some(AocError::parse("45", "Rows must all be the same length").at(2, 1))
+        );
+        assert!(Forest::new_from_text(String::new()).is_err());
     }
 
     #[test]
@@ -302,4 +214,17 @@ mod tests {
         assert_eq!(forest.at(2, 3), Ok(&5));
         assert_eq!(forest.scenic_score(2, 3), Ok(8));
     }
-}
\ No newline at end of file
+
+    proptest! {
+        #[test]
+        fn prop_display_round_trips(text in "[0-9]{5}(\n[0-9]{5}){0,5}") {
+            let forest = Forest::new_from_text(text.clone()).unwrap();
+            prop_assert_eq!(forest.to_string(), format!("{}\n", text));
+        }
+
+        #[test]
+        fn prop_parse_never_panics(text in "[0-9a\n]{0,30}") {
+            let _ = Forest::new_from_text(text);
+        }
+    }
+}
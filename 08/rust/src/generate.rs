@@ -0,0 +1,18 @@
+use common::{Generator, Rng};
+
+use crate::Day08;
+
+/// A `size` by `size` grid of tree heights.
+impl Generator for Day08 {
+    const DEFAULT_SIZE: usize = 99;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        (0..size)
+            .map(|_| {
+                let row: String = (0..size).map(|_| (b'0' + rng.below(10) as u8) as char).collect();
+                row + "\n"
+            })
+            .collect()
+    }
+}
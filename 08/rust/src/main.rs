@@ -1,9 +1,8 @@
-use std::error::Error;
+use std::process::ExitCode;
 
-use rust::run;
+use day08::Day08;
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() -> ExitCode {
     let args: std::env::Args = std::env::args();
-    run(args)?;
-    Ok(())
-}
\ No newline at end of file
+    common::exit(common::run::<Day08>(args))
+}
@@ -0,0 +1,48 @@
+use common::{AocResult, Answer, Part};
+
+const DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let heights: Vec<Vec<u32>> = contents
+        .lines()
+        .map(|line| line.chars().filter_map(|c| c.to_digit(10)).collect())
+        .collect();
+    let height_at = |x: i32, y: i32| heights.get(y as usize)?.get(x as usize).copied();
+    let mut visible = 0;
+    let mut best_score = 0;
+    for (y, row) in heights.iter().enumerate() {
+        for (x, height) in row.iter().enumerate() {
+            let mut seen_from_outside = false;
+            let mut score = 1;
+            // Walk out in each direction until the edge or a tree at least as tall.
+            for (dx, dy) in DIRECTIONS {
+                let (mut tx, mut ty) = (x as i32 + dx, y as i32 + dy);
+                let mut trees = 0;
+                loop {
+                    match height_at(tx, ty) {
+                        None => {
+                            seen_from_outside = true;
+                            break;
+                        }
+                        Some(other) => {
+                            trees += 1;
+                            if other >= *height {
+                                break;
+                            }
+                        }
+                    }
+                    (tx, ty) = (tx + dx, ty + dy);
+                }
+                score *= trees;
+            }
+            if seen_from_outside {
+                visible += 1;
+            }
+            best_score = best_score.max(score);
+        }
+    }
+    Ok(Answer::from(match part {
+        Part::A => visible,
+        Part::B => best_score,
+    }))
+}
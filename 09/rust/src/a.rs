@@ -1,11 +1,12 @@
+use common::{AocResult, Answer};
+
 use crate::rope::{Instruction, Rope};
 
-pub fn main(contents: String) -> Result<String, String> {
-    let instructions = Instruction::build_from_file_contents(contents)?;
+pub fn main(instructions: &[Instruction]) -> AocResult<Answer> {
     let mut rope = Rope::new_at_origin();
     for i in instructions {
         rope.do_instruction(i);
     }
     let tail_space_count = rope.get_count_uniq_tail_spaces();
-    Ok(tail_space_count.to_string())
+    Ok(Answer::from(tail_space_count))
 }
\ No newline at end of file
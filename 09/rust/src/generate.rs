@@ -0,0 +1,14 @@
+use common::{Generator, Rng};
+
+use crate::Day09;
+
+/// `size` moves of the rope's head.
+impl Generator for Day09 {
+    const DEFAULT_SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| format!("{} {}\n", rng.choose(&['R', 'L', 'U', 'D']), rng.range(1..=20)))
+            .collect()
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer, Part};
+
+/// Move the head a step at a time, with each knot stepping towards the one ahead whenever
+/// they stop touching.
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let length = match part {
+        Part::A => 2,
+        Part::B => 10,
+    };
+    let mut knots = vec![(0i32, 0i32); length];
+    let mut visited = HashSet::from([(0, 0)]);
+    for line in contents.lines() {
+        let (direction, steps) = line
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse(line, "Expected a direction and a distance"))?;
+        let (dx, dy) = match direction {
+            "R" => (1, 0),
+            "L" => (-1, 0),
+            "U" => (0, 1),
+            "D" => (0, -1),
+            _ => return Err(AocError::parse(direction, "Unknown direction")),
+        };
+        for _ in 0..steps.parse::<u32>()? {
+            knots[0] = (knots[0].0 + dx, knots[0].1 + dy);
+            for i in 1..length {
+                let (ahead, knot) = (knots[i - 1], knots[i]);
+                if (ahead.0 - knot.0).abs() > 1 || (ahead.1 - knot.1).abs() > 1 {
+                    knots[i] = (knot.0 + (ahead.0 - knot.0).signum(), knot.1 + (ahead.1 - knot.1).signum());
+                }
+            }
+            visited.insert(knots[length - 1]);
+        }
+    }
+    Ok(Answer::from(visited.len()))
+}
@@ -1,5 +1,7 @@
 use std::collections::hash_set::HashSet;
 
+use common::{parse_lines, AocError, AocResult};
+
 #[derive(Debug, Clone, Copy)]
 enum Direction {
     Up,
@@ -28,7 +30,7 @@ impl MultiRope {
         MultiRope { ropes }
     }
 
-    pub fn do_instruction(&mut self, instruction: Instruction) {
+    pub fn do_instruction(&mut self, instruction: &Instruction) {
         let n_ropes = self.ropes.len();
         // We have to move everything one step at a time, so we play out each step.
         for _step in 0..instruction.distance {
@@ -97,22 +99,22 @@ impl Rope {
             // The head and tail are on the same space.
             Position { x: 0, y: 0 } => {
                 self.tail_history.push(self.tail.clone());
-                return false;
+                false
             },
             // The head is directly above or below the tail by just one space.
             Position { x: 0, y } if y.abs() <= 1 => {
                 self.tail_history.push(self.tail.clone());
-                return false;
+                false
             },
             // The head is directly to the left or right of the tail by just one space.
             Position { x, y: 0 } if x.abs() <= 1 => {
                 self.tail_history.push(self.tail.clone());
-                return false;
+                false
             },
             // The head is diagonal to the tail by just one space.
             Position { x, y } if x.abs() <= 1 && y.abs() <= 1 => {
                 self.tail_history.push(self.tail.clone());
-                return false;
+                false
             }
 
             // The head is directly above or below the tail by multiple spaces.
@@ -123,7 +125,7 @@ impl Rope {
                     self.tail.y -= 1;
                 }
                 self.tail_history.push(self.tail.clone());
-                return true;
+                true
             }
             // The head is directly left or right of the tail by multiple spaces.
             Position { x, y: 0 } => {
@@ -133,7 +135,7 @@ impl Rope {
                     self.tail.x -= 1;
                 }
                 self.tail_history.push(self.tail.clone());
-                return true;
+                true
             }
             // The head is diagonal to the tail by multiple spaces.
             Position { x, y } => {
@@ -148,12 +150,12 @@ impl Rope {
                     self.tail.y -= 1;
                 }
                 self.tail_history.push(self.tail.clone());
-                return true;
+                true
             },
-        };
+        }
     }
 
-    pub fn do_instruction(&mut self, instruction: Instruction) {
+    pub fn do_instruction(&mut self, instruction: &Instruction) {
         self.move_head(instruction.direction, instruction.distance);
         // Update the tail repeatedly until it no longer needs to move.
         while self.update_tail() {}
@@ -175,9 +177,11 @@ pub struct Instruction {
 }
 
 impl Instruction {
-    fn build_from_str(s: &str) -> Result<Instruction, String> {
+    fn build_from_str(s: &str) -> AocResult<Instruction> {
         let s = s.trim();
-        assert!(s.len() >= 3);
+        if s.len() < 3 || !s.is_char_boundary(1) {
+            return Err(AocError::parse("", "Expected a direction and a distance"));
+        }
         let (direction, distance) = s.split_at(1);
         let direction = direction.trim();
         let distance = distance.trim();
@@ -186,11 +190,11 @@ impl Instruction {
             "D" => Direction::Down,
             "L" => Direction::Left,
             "R" => Direction::Right,
-            _ => return Err(String::from("Invalid direction")),
+            _ => return Err(AocError::parse(direction, "Invalid direction")),
         };
         let distance = match distance.parse::<i32>() {
             Ok(n) => n,
-            Err(_) => return Err(String::from("Invalid distance")),
+            Err(_) => return Err(AocError::parse(distance, "Invalid distance")),
         };
         Ok(Instruction {
             direction,
@@ -198,12 +202,7 @@ impl Instruction {
         })
     }
 
-    pub fn build_from_file_contents(contents: String) -> Result<Vec<Instruction>, String> {
-        let instructions = contents
-            .lines()
-            .into_iter()
-            .map(|l| Instruction::build_from_str(l))
-            .collect::<Result<Vec<Instruction>, String>>()?;
-        Ok(instructions)
+    pub fn build_from_file_contents(contents: &str) -> AocResult<Vec<Instruction>> {
+        parse_lines(contents, Instruction::build_from_str)
     }
 }
@@ -1,9 +1,10 @@
+use common::{AocResult, Answer};
+
 use crate::instruction::Instruction;
 use crate::cpu::CpuState;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let lines: Vec<String> = contents.lines().map(|s| s.to_string()).collect();
-    let mut instructions = Instruction::parse_lines(lines)?;
+pub fn main(instructions: &[Instruction]) -> AocResult<Answer> {
+    let mut instructions = instructions.to_vec();
 
     let cycles_of_note: Vec<usize> = vec![20, 60, 100, 140, 180, 220];
     let mut state = CpuState::new();
@@ -14,14 +15,15 @@ pub fn main(contents: String) -> Result<String, String> {
         if state.starting_cycle > *cycles_of_note.last().unwrap() {
             break;
         }
-        println!("Cycle {}: {}  =>  {}", &state.starting_cycle, &state.value, &state.signal_strength());
+        log::trace!("Cycle {}: {}  =>  {}", &state.starting_cycle, &state.value, &state.signal_strength());
         if cycles_of_note.contains(&state.starting_cycle) {
             total_strength += state.signal_strength();
         }
-        if let Err(_) = state.tick() {
+        if state.is_idle() {
             break;
         }
+        state.tick()?;
     }
 
-    Ok(total_strength.to_string())
+    Ok(Answer::from(total_strength))
 }
\ No newline at end of file
@@ -1,33 +1,54 @@
+use common::{AocResult, Answer};
+
 use crate::instruction::Instruction;
 use crate::cpu::CpuState;
 
 const LINE_LENGTH: usize = 40;
 
-
-pub fn main(contents: String) -> Result<String, String> {
-    let lines: Vec<String> = contents.lines().map(|s| s.to_string()).collect();
-    let mut instructions = Instruction::parse_lines(lines)?;
+pub fn main(instructions: &[Instruction]) -> AocResult<Answer> {
+    let mut instructions = instructions.to_vec();
 
     let mut state = CpuState::new();
     state.queue_instructions(&mut instructions);
 
-    let mut text = String::from("\n");
+    let mut rows = vec![];
+    let mut row = String::new();
     loop {
         let pixel = (state.starting_cycle - 1) % LINE_LENGTH;
         let sprite_pos = state.value;
 
-        if (pixel as i32 - sprite_pos).abs() <= 1 {
-            text.push_str("#");
+        // Only cycles that run an instruction get drawn.
+        if state.is_idle() {
+            break;
+        }
+        state.tick()?;
+        if (pixel as i64 - i64::from(sprite_pos)).abs() <= 1 {
+            row.push('#');
         } else {
-            text.push_str(" ");
+            row.push(' ');
         }
-        if (pixel + 1) % LINE_LENGTH == 0 {
-            text.push_str("\n");
-        } 
-        if let Err(_) = state.tick() {
-            break;
+        if (pixel + 1).is_multiple_of(LINE_LENGTH) {
+            rows.push(row);
+            row = String::new();
         }
     }
 
-    Ok(text)
-}
\ No newline at end of file
+    if !row.is_empty() {
+        rows.push(row);
+    }
+
+    Ok(Answer::Art(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stops_drawing_with_the_last_instruction() {
+        // Forty noops fill exactly one row, with nothing drawn after them.
+        let instructions = Instruction::parse_lines(&"noop\n".repeat(40)).unwrap();
+        let row = format!("###{}", " ".repeat(37));
+        assert_eq!(main(&instructions), Ok(Answer::Art(vec![row])));
+    }
+}
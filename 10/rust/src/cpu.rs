@@ -1,3 +1,5 @@
+use common::{AocError, AocResult};
+
 use crate::instruction::Instruction;
 
 pub struct CpuState {
@@ -22,9 +24,14 @@ impl CpuState {
         self.instructions.append(instructions)
     }
 
-    pub fn tick(&mut self) -> Result<(), String> {
-        if self.instructions.is_empty() && self.instruction_in_progress.is_none(){
-            return Err("No instructions queued".to_string())
+    // True once every queued instruction has finished.
+    pub fn is_idle(&self) -> bool {
+        self.instructions.is_empty() && self.instruction_in_progress.is_none()
+    }
+
+    pub fn tick(&mut self) -> AocResult<()> {
+        if self.is_idle() {
+            return Err(AocError::solve("No instructions queued"))
         }
         self.starting_cycle += 1;
 
@@ -32,7 +39,7 @@ impl CpuState {
         if let Some((instr, cycles)) = self.instruction_in_progress.take() {
             if cycles + 1 == instr.cycles() {
                 self.instruction_in_progress = None;
-                self.value = instr.transform_value(self.value);
+                self.value = instr.transform_value(self.value)?;
             } else {
                 self.instruction_in_progress = Some((instr, cycles + 1));
             }
@@ -42,15 +49,15 @@ impl CpuState {
         // Handle a new instruction.
         let next_instr = self.instructions.remove(0);
         if next_instr.cycles() == 1 {
-            self.value = next_instr.transform_value(self.value);
+            self.value = next_instr.transform_value(self.value)?;
         } else {
             self.instruction_in_progress = Some((next_instr, 1));
         }
         Ok(())
     }
 
-    pub fn signal_strength(&self) -> i32 {
-        self.value * (self.starting_cycle as i32)
+    pub fn signal_strength(&self) -> i64 {
+        i64::from(self.value) * (self.starting_cycle as i64)
     }
 }
 
@@ -69,26 +76,45 @@ mod tests {
         let mut cpu = CpuState::new();
         cpu.queue_instructions(&mut ins);
 
-        assert_eq!(cpu.starting_cycle, 0);
+        assert_eq!(cpu.starting_cycle, 1);
         // Cycle 1; do Noop
         cpu.tick().unwrap();
-        assert_eq!(cpu.starting_cycle, 1);
+        assert_eq!(cpu.starting_cycle, 2);
         assert_eq!(cpu.value, 1);
         // Cycle 2; first half of AddX(3)
         cpu.tick().unwrap();
-        assert_eq!(cpu.starting_cycle, 2);
+        assert_eq!(cpu.starting_cycle, 3);
         assert_eq!(cpu.value, 1);
         // Cycle 3; complete AddX(3)
         cpu.tick().unwrap();
-        assert_eq!(cpu.starting_cycle, 3);
+        assert_eq!(cpu.starting_cycle, 4);
         assert_eq!(cpu.value, 4);
         // Cycle 4; first half of AddX(-5)
         cpu.tick().unwrap();
-        assert_eq!(cpu.starting_cycle, 4);
+        assert_eq!(cpu.starting_cycle, 5);
         assert_eq!(cpu.value, 4);
         // Cycle 5; complete AddX(-5)
         cpu.tick().unwrap();
-        assert_eq!(cpu.starting_cycle, 5);
+        assert_eq!(cpu.starting_cycle, 6);
         assert_eq!(cpu.value, -1);
     }
+
+    #[test]
+    fn test_overflow() {
+        // X starts at 1, so the first addx already goes past i32::MAX.
+        let mut ins = Instruction::parse_lines("addx 2147483647\naddx 1").unwrap();
+        let mut cpu = CpuState::new();
+        cpu.queue_instructions(&mut ins);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.tick(), Err(AocError::solve("addx 2147483647 overflows X")));
+
+        let mut ins = Instruction::parse_lines("addx 2147483646\naddx 1").unwrap();
+        let mut cpu = CpuState::new();
+        cpu.queue_instructions(&mut ins);
+        for _ in 0..3 {
+            cpu.tick().unwrap();
+        }
+        assert_eq!(cpu.value, i32::MAX);
+        assert_eq!(cpu.tick(), Err(AocError::solve("addx 1 overflows X")));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use common::{Generator, Rng};
+
+use crate::Day10;
+
+/// Enough cycles to draw the whole screen.
+const SCREEN_CYCLES: usize = 240;
+
+/// A CPU program of about `size` instructions, padded with `noop`s to run for the whole
+/// screen. The register stays on the screen, so the sprite is drawn somewhere on each row.
+impl Generator for Day10 {
+    const DEFAULT_SIZE: usize = 140;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut text = String::new();
+        let (mut cycles, mut instructions, mut x) = (0, 0, 1);
+        while instructions < size || cycles < SCREEN_CYCLES {
+            if rng.chance(1, 3) {
+                text.push_str("noop\n");
+                cycles += 1;
+            } else {
+                let value = rng.range(-x.min(15)..=(39 - x).min(15));
+                x += value;
+                text.push_str(&format!("addx {}\n", value));
+                cycles += 2;
+            }
+            instructions += 1;
+        }
+        text
+    }
+}
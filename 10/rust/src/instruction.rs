@@ -1,27 +1,25 @@
+use common::{AocError, AocResult};
+
+#[derive(Debug, Clone)]
 pub enum Instruction {
     Noop,
     AddX(i32),
 }
 
 impl Instruction {
-    pub fn parse_lines(lines: Vec<String>) -> Result<Vec<Instruction>, String> {
-        let mut instructions: Vec<Instruction> = Vec::new();
-        for line in lines {
-            let instruction = Instruction::parse_line(line)?;
-            instructions.push(instruction);
-        }
-        Ok(instructions)
+    pub fn parse_lines(contents: &str) -> AocResult<Vec<Instruction>> {
+        common::parse_lines(contents, Instruction::parse_line)
     }
 
-    pub fn parse_line(line: String) -> Result<Instruction, String> {
+    pub fn parse_line(line: &str) -> AocResult<Instruction> {
         let parts = line.split_whitespace().collect::<Vec<&str>>();
         match parts[..] {
             ["noop"] => Ok(Instruction::Noop),
             ["addx", x] => {
-                let x = x.parse::<i32>().map_err(|e| e.to_string())?;
+                let x = x.parse::<i32>().map_err(|e| AocError::parse(x, &e.to_string()))?;
                 Ok(Instruction::AddX(x))
             }
-            _ => Err(format!("Invalid instruction: {}", line)),
+            _ => Err(AocError::parse("", "Invalid instruction")),
         }
     }
 
@@ -32,13 +30,13 @@ impl Instruction {
         }
     }
 
-    pub fn transform_value(&self, value: i32) -> i32 {
+    pub fn transform_value(&self, value: i32) -> AocResult<i32> {
         match self {
             Instruction::Noop => {
-                value
+                Ok(value)
             },
             Instruction::AddX(x) => {
-                value + x
+                value.checked_add(*x).ok_or_else(|| AocError::Solve(format!("addx {} overflows X", x)))
             },
         }
     }
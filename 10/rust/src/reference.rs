@@ -0,0 +1,47 @@
+use common::{AocError, AocResult, Answer, Part};
+
+const LINE_LENGTH: usize = 40;
+
+/// The X register during each cycle, in order.
+fn register_by_cycle(contents: &str) -> AocResult<Vec<i32>> {
+    let mut x = 1;
+    let mut values = Vec::new();
+    for line in contents.lines() {
+        match line.split(' ').collect::<Vec<_>>()[..] {
+            ["noop"] => values.push(x),
+            ["addx", value] => {
+                values.push(x);
+                values.push(x);
+                x += value.parse::<i32>()?;
+            }
+            _ => return Err(AocError::parse(line, "Unknown instruction")),
+        }
+    }
+    Ok(values)
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let values = register_by_cycle(contents)?;
+    match part {
+        Part::A => {
+            let strength: i32 = [20, 60, 100, 140, 180, 220]
+                .iter()
+                .filter(|cycle| **cycle <= values.len())
+                .map(|cycle| *cycle as i32 * values[cycle - 1])
+                .sum();
+            Ok(Answer::from(strength))
+        }
+        Part::B => {
+            let pixels: Vec<char> = values
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    let column = (i % LINE_LENGTH) as i32;
+                    if (column - x).abs() <= 1 { '#' } else { ' ' }
+                })
+                .collect();
+            let rows = pixels.chunks(LINE_LENGTH).map(|row| row.iter().collect()).collect();
+            Ok(Answer::Art(rows))
+        }
+    }
+}
@@ -1,10 +1,9 @@
+use common::{AocResult, Answer};
+
 use crate::monkey::Monkey;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let monkey_texts = contents.split("\n\n");
-    let mut monkeys = monkey_texts
-        .map(|m| Monkey::build_from_text(m))
-        .collect::<Result<Vec<Monkey>, String>>()?;
+pub fn main(monkeys: &[Monkey]) -> AocResult<Answer> {
+    let mut monkeys = monkeys.to_vec();
     for _round in 1..21 {
         for i in 0..monkeys.len() {
             let current_monkey = &mut monkeys[i];
@@ -25,5 +24,5 @@ pub fn main(contents: String) -> Result<String, String> {
     inspection_counts.reverse();
     let (m1, m2) = (inspection_counts[0], inspection_counts[1]);
 
-    Ok((m1 * m2).to_string())
+    Ok(Answer::from(m1 * m2))
 }
@@ -1,3 +1,5 @@
+use common::{budget, AocResult, Answer};
+
 use crate::monkey::Monkey;
 
 const N_ROUNDS: usize = 10_000;
@@ -11,21 +13,19 @@ fn gcd(a: i32, b: i32) -> i32 {
 
 fn lcm(numbers: Vec<i32>) -> i32 {
     let mut lcm = numbers[0];
-    for i in 1..numbers.len() {
-        lcm = (lcm * numbers[i]) / gcd(lcm, numbers[i]);
+    for n in &numbers[1..] {
+        lcm = (lcm * n) / gcd(lcm, *n);
     }
     lcm
 }
 
-pub fn main(contents: String) -> Result<String, String> {
-    let monkey_texts = contents.split("\n\n");
-    let mut monkeys = monkey_texts
-        .map(|m| Monkey::build_from_text(m))
-        .collect::<Result<Vec<Monkey>, String>>()?;
+pub fn main(monkeys: &[Monkey]) -> AocResult<Answer> {
+    let mut monkeys = monkeys.to_vec();
     let divisors = monkeys.iter().map(|m| m.divisor).collect::<Vec<i32>>();
     let lcm = lcm(divisors);
     monkeys.iter_mut().for_each(|m| m.set_lcm(lcm));
     for _round in 1..(N_ROUNDS + 1) {
+        budget::checkpoint()?;
         for i in 0..monkeys.len() {
             let current_monkey = &mut monkeys[i];
             let items = current_monkey.take_turn(false);
@@ -43,8 +43,8 @@ pub fn main(contents: String) -> Result<String, String> {
     // Sort them by inspection count.
     inspection_counts.sort();
     inspection_counts.reverse();
-    println!("Inspection counts: {:?}", inspection_counts);
+    log::debug!("Inspection counts: {:?}", inspection_counts);
     let (m1, m2) = (inspection_counts[0], inspection_counts[1]);
 
-    Ok((m1 * m2).to_string())
+    Ok(Answer::from(m1 * m2))
 }
@@ -0,0 +1,72 @@
+use common::{Generator, Rng};
+
+use crate::Day11;
+
+/// Distinct primes keep the monkeys' tests independent, and their product small enough to
+/// reduce worry levels by.
+const DIVISORS: [i64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+const MULTIPLYING_MONKEYS: usize = 2;
+
+/// `size` monkeys (between 2 and 9), each throwing to two others.
+///
+/// Part A never reduces worry levels by the tests' divisors, so they only stay manageable
+/// because of how the real inputs are built, and these follow suit: at most one monkey squares worry
+/// levels, and it throws to monkeys that never throw back towards it, so each item is
+/// squared at most once. Two more multiply by small numbers, and the rest add.
+impl Generator for Day11 {
+    const DEFAULT_SIZE: usize = 8;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let count = size.clamp(2, DIVISORS.len());
+        let mut divisors = DIVISORS;
+        rng.shuffle(&mut divisors);
+        let mut order: Vec<usize> = (0..count).collect();
+        rng.shuffle(&mut order);
+        // The squaring monkey, and the monkeys downstream of it that only throw among
+        // themselves. It takes three for each of them to have two others to throw to.
+        let (squarer, downstream) = if count >= 4 {
+            let downstream = 3.max((count - 1) / 2);
+            (Some(order[0]), order[1..=downstream].to_vec())
+        } else {
+            (None, Vec::new())
+        };
+        let multipliers: Vec<usize> = order
+            .iter()
+            .filter(|id| Some(**id) != squarer)
+            .take(MULTIPLYING_MONKEYS)
+            .copied()
+            .collect();
+
+        let mut monkeys = Vec::new();
+        for (id, divisor) in divisors.iter().take(count).enumerate() {
+            let items: Vec<String> =
+                (0..rng.range(1..=8)).map(|_| rng.range(50..=99).to_string()).collect();
+            let operation = if Some(id) == squarer {
+                String::from("old * old")
+            } else if multipliers.contains(&id) {
+                format!("old * {}", rng.range(2..=7))
+            } else {
+                format!("old + {}", rng.range(1..=8))
+            };
+            let mut targets: Vec<usize> = if Some(id) == squarer || downstream.contains(&id) {
+                downstream.clone()
+            } else {
+                (0..count).collect()
+            };
+            targets.retain(|other| *other != id);
+            rng.shuffle(&mut targets);
+            // With only two monkeys, both throws go to the other one.
+            let on_false = *targets.get(1).unwrap_or(&targets[0]);
+            monkeys.push(format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                id,
+                items.join(", "),
+                operation,
+                divisor,
+                targets[0],
+                on_false
+            ));
+        }
+        monkeys.join("\n")
+    }
+}
@@ -1,14 +1,17 @@
-use std::{num::ParseIntError, fmt::Display};
+use std::{fmt::Display, rc::Rc};
 
-#[derive(Debug, PartialEq, Eq)]
+use common::{AocError, AocResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Item(u128);
 
 type ItemUpdater = dyn Fn(Item) -> Item;
 
+#[derive(Clone)]
 pub struct Monkey {
     pub id: usize,
     items: Vec<Item>,
-    updater: Box<ItemUpdater>,
+    updater: Rc<ItemUpdater>,
     pub divisor: i32,
     // Which monkeys to throw items to on true/false test result.
     on_true: usize,
@@ -31,7 +34,7 @@ impl Monkey {
         Monkey {
             id,
             items: vec![],
-            updater,
+            updater: Rc::from(updater),
             divisor,
             on_true,
             on_false,
@@ -54,7 +57,7 @@ impl Monkey {
     }
 
     pub fn pop_item(&mut self, reduce: bool) -> Option<(Item, usize)> {
-        if self.items.len() == 0 {
+        if self.items.is_empty() {
             return None;
         }
         // Get the first item and adjust its worry value.
@@ -85,70 +88,74 @@ impl Monkey {
         items
     }
 
-    pub fn build_from_text(text: &str) -> Result<Monkey, String> {
+    pub fn build_from_text(text: &str) -> AocResult<Monkey> {
         let lines: Vec<&str> = text.lines().collect();
+        if lines.len() != 6 {
+            return Err(AocError::parse("", "Expected six lines per monkey").at(1, 1));
+        }
+        // Parse the field on line `i`, blaming that line if it goes wrong.
+        fn field<T>(
+            lines: &[&str],
+            i: usize,
+            parse: impl Fn(&str) -> Option<T>,
+            what: &str,
+        ) -> AocResult<T> {
+            parse(lines[i]).ok_or_else(|| {
+                AocError::parse("", &format!("Could not parse {}", what)).on_line(i + 1, lines[i])
+            })
+        }
         // ID
-        let id_with_colon = lines[0]
-            .split_whitespace()
-            .nth(1)
-            .ok_or("Could not parse id")?;
-        let id = id_with_colon
-            .split(":")
-            .nth(0)
-            .ok_or("Could not parse id")?;
-        let id = id.parse::<usize>().map_err(|_| "Could not parse id")?;
+        let id = field(&lines, 0, |line| {
+            line.split_whitespace()
+                .nth(1)?
+                .split(":")
+                .next()?
+                .parse::<usize>()
+                .ok()
+        }, "id")?;
         // Starting Items
-        let items = lines[1]
-            .split(": ")
-            .nth(1)
-            .ok_or("Could not parse starting items")?;
-        let items = items
-            .split(", ")
-            .map(|i| i.parse::<u128>())
-            .collect::<Result<Vec<u128>, ParseIntError>>()
-            .map_err(|_| "Could not parse starting items")?;
-        let items = items.iter().map(|i| Item(*i)).collect();
+        let items = field(&lines, 1, |line| {
+            line.split(": ")
+                .nth(1)?
+                .split(", ")
+                .map(|i| i.parse::<u128>().ok().map(Item))
+                .collect::<Option<Vec<Item>>>()
+        }, "starting items")?;
         // Updater
-        let expr = lines[2]
-            .split("new = ")
-            .nth(1)
-            .ok_or("Could not parse updater")?;
-        let tokens = expr.split_whitespace().collect::<Vec<&str>>();
-        let updater: Box<ItemUpdater> = match tokens[..] {
-            ["old", "+", "old"] => Box::new(|i: Item| Item(i.0 + i.0)),
-            ["old", "*", "old"] => Box::new(|i: Item| Item(i.0 * i.0)),
-            ["old", "*", x] | [x, "*", "old"] => {
-                let x = x.parse::<u128>().map_err(|_| "Could not parse updater")?;
-                Box::new(move |i: Item| Item(i.0 * x))
-            }
-            ["old", "+", x] | [x, "+", "old"] => {
-                let x = x.parse::<u128>().map_err(|_| "Could not parse updater")?;
-                Box::new(move |i: Item| Item(i.0 + x))
-            }
-            _ => return Err("Could not parse updater".to_string()),
-        };
+        let updater = field(&lines, 2, |line| {
+            let expr = line.split("new = ").nth(1)?;
+            let tokens = expr.split_whitespace().collect::<Vec<&str>>();
+            let updater: Rc<ItemUpdater> = match tokens[..] {
+                ["old", "+", "old"] => Rc::new(|i: Item| Item(i.0 + i.0)),
+                ["old", "*", "old"] => Rc::new(|i: Item| Item(i.0 * i.0)),
+                ["old", "*", x] | [x, "*", "old"] => {
+                    let x = x.parse::<u128>().ok()?;
+                    Rc::new(move |i: Item| Item(i.0 * x))
+                }
+                ["old", "+", x] | [x, "+", "old"] => {
+                    let x = x.parse::<u128>().ok()?;
+                    Rc::new(move |i: Item| Item(i.0 + x))
+                }
+                _ => return None,
+            };
+            Some(updater)
+        }, "updater")?;
         // Divisor
-        let divisor = lines[3]
-            .split("Test: divisible by ")
-            .nth(1)
-            .ok_or("Could not parse divisor")?;
-        let divisor = divisor
-            .parse::<i32>()
-            .map_err(|_| "Could not parse divisor")?;
+        let divisor = field(&lines, 3, |line| {
+            line.split("Test: divisible by ")
+                .nth(1)?
+                .parse::<i32>()
+                .ok()
+                .filter(|d| *d > 0)
+        }, "divisor")?;
         // On True
-        let on_true = lines[4]
-            .split("throw to monkey ")
-            .nth(1)
-            .ok_or("Could not parse on_true")?
-            .parse::<usize>()
-            .map_err(|_| "Could not parse on_true")?;
+        let on_true = field(&lines, 4, |line| {
+            line.split("throw to monkey ").nth(1)?.parse::<usize>().ok()
+        }, "on_true")?;
         // On False
-        let on_false = lines[5]
-            .split("throw to monkey ")
-            .nth(1)
-            .ok_or("Could not parse on_false")?
-            .parse::<usize>()
-            .map_err(|_| "Could not parse on_false")?;
+        let on_false = field(&lines, 5, |line| {
+            line.split("throw to monkey ").nth(1)?.parse::<usize>().ok()
+        }, "on_false")?;
 
         Ok(Monkey {
             id,
@@ -162,6 +169,11 @@ impl Monkey {
         })
     }
 
+    /// The monkeys this one can throw to.
+    pub fn targets(&self) -> [usize; 2] {
+        [self.on_true, self.on_false]
+    }
+
     pub fn set_lcm(&mut self, lcm: i32) {
         self.lcm = Some(lcm);
     }
@@ -191,10 +203,10 @@ mod tests {
     #[test]
     fn test_test() {
         let m = Monkey::new(0, Box::new(|i| i), 2, 0, 0, 0, None);
-        assert_eq!(m.test_item(&Item(0)), true);
-        assert_eq!(m.test_item(&Item(1)), false);
-        assert_eq!(m.test_item(&Item(2)), true);
-        assert_eq!(m.test_item(&Item(3)), false);
+        assert!(m.test_item(&Item(0)));
+        assert!(!m.test_item(&Item(1)));
+        assert!(m.test_item(&Item(2)));
+        assert!(!m.test_item(&Item(3)));
     }
 
     #[test]
@@ -223,5 +235,14 @@ mod tests {
         assert_eq!(m.divisor, 17);
         assert_eq!(m.on_true, 0);
         assert_eq!(m.on_false, 1);
+
+        let bad_divisor = text.replace("by 17", "by seventeen");
+        assert_eq!(
+            Monkey::build_from_text(&bad_divisor).err(),
+            Some(
+                AocError::parse("        Test: divisible by seventeen", "Could not parse divisor")
+                    .at(4, 1)
+            )
+        );
     }
 }
@@ -0,0 +1,114 @@
+use common::{AocError, AocResult, Answer, Part};
+
+enum Operation {
+    Add(u128),
+    Multiply(u128),
+    Square,
+}
+
+struct Monkey {
+    items: Vec<u128>,
+    operation: Operation,
+    divisor: u128,
+    on_true: usize,
+    on_false: usize,
+}
+
+impl Operation {
+    fn apply(&self, old: u128) -> Option<u128> {
+        match self {
+            Operation::Add(n) => old.checked_add(*n),
+            Operation::Multiply(n) => old.checked_mul(*n),
+            Operation::Square => old.checked_mul(old),
+        }
+    }
+}
+
+fn parse(contents: &str) -> AocResult<Vec<Monkey>> {
+    let mut monkeys = Vec::new();
+    for block in contents.split("\n\n") {
+        let lines: Vec<&str> = block.lines().map(|line| line.trim()).collect();
+        let field = |i: usize, prefix: &str| -> AocResult<&str> {
+            lines
+                .get(i)
+                .and_then(|line| line.strip_prefix(prefix))
+                .ok_or_else(|| AocError::parse(block, &format!("Expected {:?}", prefix)))
+        };
+        let items = field(1, "Starting items: ")?
+            .split(", ")
+            .map(|item| item.parse::<u128>())
+            .collect::<Result<_, _>>()?;
+        let operation = match field(2, "Operation: new = old ")?.split_once(' ') {
+            Some(("*", "old")) => Operation::Square,
+            Some(("*", n)) => Operation::Multiply(n.parse()?),
+            Some(("+", n)) => Operation::Add(n.parse()?),
+            _ => return Err(AocError::parse(lines[2], "Unknown operation")),
+        };
+        monkeys.push(Monkey {
+            items,
+            operation,
+            divisor: field(3, "Test: divisible by ")?.parse()?,
+            on_true: field(4, "If true: throw to monkey ")?.parse()?,
+            on_false: field(5, "If false: throw to monkey ")?.parse()?,
+        });
+    }
+    Ok(monkeys)
+}
+
+/// The two busiest monkeys' inspection counts, multiplied.
+fn monkey_business(mut inspections: Vec<usize>) -> Answer {
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    Answer::from(inspections[0] * inspections.get(1).unwrap_or(&0))
+}
+
+/// Part A as told: the worry levels themselves, failing rather than overflowing.
+fn relieved(mut monkeys: Vec<Monkey>) -> AocResult<Answer> {
+    let mut inspections = vec![0; monkeys.len()];
+    for _ in 0..20 {
+        for i in 0..monkeys.len() {
+            for item in std::mem::take(&mut monkeys[i].items) {
+                inspections[i] += 1;
+                let monkey = &monkeys[i];
+                let worry = monkey.operation.apply(item).ok_or(AocError::solve("Worry overflowed"))? / 3;
+                let to = if worry % monkey.divisor == 0 { monkey.on_true } else { monkey.on_false };
+                monkeys[to].items.push(worry);
+            }
+        }
+    }
+    Ok(monkey_business(inspections))
+}
+
+/// Part B keeps each worry level as its remainder by every monkey's divisor, which is all
+/// the tests look at, and which adding and multiplying keep track of.
+fn worried(monkeys: Vec<Monkey>) -> AocResult<Answer> {
+    let divisors: Vec<u128> = monkeys.iter().map(|m| m.divisor).collect();
+    let mut held: Vec<Vec<Vec<u128>>> = monkeys
+        .iter()
+        .map(|m| m.items.iter().map(|item| divisors.iter().map(|d| item % d).collect()).collect())
+        .collect();
+    let mut inspections = vec![0; monkeys.len()];
+    for _ in 0..10_000 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for remainders in std::mem::take(&mut held[i]) {
+                inspections[i] += 1;
+                let remainders: Vec<u128> = remainders
+                    .iter()
+                    .zip(&divisors)
+                    .map(|(r, d)| monkey.operation.apply(*r).map(|worry| worry % d))
+                    .collect::<Option<_>>()
+                    .ok_or(AocError::solve("Worry overflowed"))?;
+                let to = if remainders[i] == 0 { monkey.on_true } else { monkey.on_false };
+                held[to].push(remainders);
+            }
+        }
+    }
+    Ok(monkey_business(inspections))
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let monkeys = parse(contents)?;
+    match part {
+        Part::A => relieved(monkeys),
+        Part::B => worried(monkeys),
+    }
+}
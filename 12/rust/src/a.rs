@@ -1,7 +1,8 @@
+use common::{AocResult, Answer};
+
 use crate::heightmap::HeightMap;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let map = HeightMap::build_from_str(&contents)?;
+pub fn main(map: &HeightMap) -> AocResult<Answer> {
     let result = map.find_shortest_path()?;
-    Ok(format!("{}", result))
+    Ok(Answer::from(result))
 }
\ No newline at end of file
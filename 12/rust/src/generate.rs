@@ -0,0 +1,34 @@
+use common::{Generator, Rng};
+
+use crate::Day12;
+
+/// A heightmap `size` squares wide (at least 26) and a quarter as tall. One row climbs
+/// steadily from S on the left edge to E on the right, so there's always a way up; the
+/// rest roughly follows it, with some noise.
+impl Generator for Day12 {
+    const DEFAULT_SIZE: usize = 160;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = size.max(26);
+        let height = (width / 4).max(5);
+        let path_row = rng.below(height);
+        let climb = |x: usize| (x * 25 / (width - 1)) as i64;
+        let mut text = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                let elevation = if y == path_row {
+                    climb(x)
+                } else {
+                    (climb(x) + rng.range(-4..=2)).clamp(0, 25)
+                };
+                text.push(match (y == path_row, x) {
+                    (true, 0) => 'S',
+                    (true, x) if x == width - 1 => 'E',
+                    _ => (b'a' + elevation as u8) as char,
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::{HashMap, VecDeque};
+
+use common::{AocError, AocResult, Answer, Part};
+
+type Square = (usize, usize);
+
+fn elevation(c: char) -> u8 {
+    match c {
+        'S' => b'a',
+        'E' => b'z',
+        c => c as u8,
+    }
+}
+
+/// Breadth-first from `start`: the fewest steps to E, if it can be reached.
+fn steps_to_end(squares: &HashMap<Square, char>, start: Square) -> Option<usize> {
+    let mut steps = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some((x, y)) = queue.pop_front() {
+        let here = squares[&(x, y)];
+        if here == 'E' {
+            return Some(steps[&(x, y)]);
+        }
+        let neighbours = [(x + 1, y), (x.wrapping_sub(1), y), (x, y + 1), (x, y.wrapping_sub(1))];
+        for next in neighbours {
+            let Some(there) = squares.get(&next) else {
+                continue;
+            };
+            if elevation(*there) <= elevation(here) + 1 && !steps.contains_key(&next) {
+                steps.insert(next, steps[&(x, y)] + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Part B searches separately from every lowest square.
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let squares: HashMap<Square, char> = contents
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| line.chars().enumerate().map(move |(x, c)| ((x, y), c)))
+        .collect();
+    let starts = squares.iter().filter(|(_, c)| match part {
+        Part::A => **c == 'S',
+        Part::B => elevation(**c) == b'a',
+    });
+    starts
+        .filter_map(|(start, _)| steps_to_end(&squares, *start))
+        .min()
+        .map(Answer::from)
+        .ok_or(AocError::NoAnswer)
+}
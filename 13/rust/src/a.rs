@@ -1,26 +1,29 @@
-use crate::packet::Packet;
+use common::{AocResult, Answer};
 
-pub fn main(contents: String) -> Result<String, String> {
-    let pairs: Vec<(Packet, Packet)> = contents
-        .trim()
-        .split("\n\n")
-        .map(|lines| {
-            let lines: Vec<_> = lines.split("\n").collect();
-            if lines.len() != 2 {
-                return Err(format!("Invalid input: {}", lines.join("\n")));
-            }
-            let left_packet = Packet::build_from_text(lines[0])?;
-            let right_packet = Packet::build_from_text(lines[1])?;
-            Ok((left_packet, right_packet))
-        })
-        .collect::<Result<Vec<(Packet, Packet)>, String>>()?;
+use crate::packet::Packet;
 
+pub fn main(pairs: &[(Packet, Packet)]) -> AocResult<Answer> {
     let mut sum = 0;
     for (index, (p1, p2)) in pairs.iter().enumerate() {
-        if p1 <= p2 {
+        // Packets that compare equal never decide the order, so they don't count.
+        if p1 < p2 {
             // Adjust for one-based indexing.
             sum += index + 1;
         }
     }
-    Ok(sum.to_string())
-}
\ No newline at end of file
+    Ok(Answer::from(sum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packet::parse_pairs;
+
+    #[test]
+    fn test_equal_pairs_are_not_in_order() {
+        // The second pair is equal (an integer against a list of just it), so only the
+        // first counts.
+        let pairs = parse_pairs("[1]\n[2]\n\n[3]\n[[3]]\n\n[5]\n[4]\n").unwrap();
+        assert_eq!(main(&pairs), Ok(Answer::from(1)));
+    }
+}
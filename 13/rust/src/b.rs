@@ -1,29 +1,41 @@
+use common::{AocError, AocResult, Answer};
+
 use crate::packet::Packet;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let mut packets = contents
-        .trim()
-        .split("\n")
-        .filter(|line| !line.is_empty())
-        .map(|line| Packet::build_from_text(line))
-        .collect::<Result<Vec<Packet>, String>>()?;
-    let mut divider_packets = vec![
-        Packet::build_from_text("[[2]]")?,
-        Packet::build_from_text("[[6]]")?,
-    ];
-    packets.append(&mut divider_packets);
+pub fn main(pairs: &[(Packet, Packet)]) -> AocResult<Answer> {
+    let divider_one = Packet::build_from_text("[[2]]")?;
+    let divider_two = Packet::build_from_text("[[6]]")?;
+    // The dividers go first, so that the stable sort keeps them ahead of any packets that
+    // compare equal to them, like `[2]`.
+    let mut packets: Vec<&Packet> = vec![&divider_one, &divider_two];
+    packets.extend(pairs.iter().flat_map(|(l, r)| [l, r]));
     packets.sort();
 
     // Figure out where the divider packets wound up.
     let divider_one_index = packets
         .iter()
-        .position(|p| p == &Packet::build_from_text("[[2]]").unwrap())
-        .ok_or(String::from("divider packet not found"))?;
+        .position(|p| *p == &divider_one)
+        .ok_or(AocError::solve("divider packet not found"))?;
     let divider_two_index = packets
         .iter()
-        .position(|p| p == &Packet::build_from_text("[[6]]").unwrap())
-        .ok_or(String::from("divider packet not found"))?;
+        .position(|p| *p == &divider_two)
+        .ok_or(AocError::solve("divider packet not found"))?;
     // Adjust for 1-based indexing.
     let signal = (divider_one_index + 1) * (divider_two_index + 1);
-    Ok(signal.to_string())
-}
\ No newline at end of file
+    Ok(Answer::from(signal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packets_equal_to_a_divider_come_after_it() {
+        let pairs = vec![
+            (Packet::build_from_text("[6]").unwrap(), Packet::build_from_text("[2]").unwrap()),
+            (Packet::build_from_text("[1]").unwrap(), Packet::build_from_text("[9]").unwrap()),
+        ];
+        // [1], [[2]], [2], [[6]], [6], [9]
+        assert_eq!(main(&pairs), Ok(Answer::from(2 * 4)));
+    }
+}
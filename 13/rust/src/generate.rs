@@ -0,0 +1,31 @@
+use common::{Generator, Rng};
+
+use crate::Day13;
+
+const MAX_DEPTH: usize = 4;
+
+/// `size` pairs of packets.
+impl Generator for Day13 {
+    const DEFAULT_SIZE: usize = 150;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let pairs: Vec<String> = (0..size)
+            .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+            .collect();
+        pairs.join("\n")
+    }
+}
+
+/// A list of numbers and lists, nested at most `MAX_DEPTH` deep.
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<String> = (0..rng.range(0..=5))
+        .map(|_| {
+            if depth < MAX_DEPTH && rng.chance(1, 3) {
+                packet(rng, depth + 1)
+            } else {
+                rng.range(0..=10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
@@ -1,8 +1,9 @@
 use crate::token::Token;
+use common::{AocError, AocResult};
 use std::fmt::Display;
 
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Packet {
     items: Vec<PacketItem>,
 }
@@ -12,14 +13,17 @@ impl Packet {
         Packet { items }
     }
 
-    pub fn build_from_tokens(tokens: &mut Vec<Token>) -> Result<Self, String> {
+    pub fn build_from_tokens(tokens: &mut Vec<Token>) -> AocResult<Self> {
         let mut items = Vec::new();
         // Opening bracket.
-        match tokens[0] {
-            Token::LB => tokens.remove(0),
-            _ => return Err("Expected LB".to_string()),
+        match tokens.first() {
+            Some(Token::LB) => tokens.remove(0),
+            _ => return Err(AocError::parse("", "Expected [")),
         };
         loop {
+            if tokens.is_empty() {
+                return Err(AocError::parse("", "Expected ]"));
+            }
             match tokens.remove(0) {
                 Token::Comma => continue,
                 Token::Number(n) => {
@@ -37,12 +41,35 @@ impl Packet {
         Ok(Packet { items })
     }
 
-    pub fn build_from_text(text: &str) -> Result<Packet, String> {
+    pub fn build_from_text(text: &str) -> AocResult<Packet> {
         let mut tokens = Token::tokenize(text)?;
-        Self::build_from_tokens(&mut tokens)
+        let packet = Self::build_from_tokens(&mut tokens)?;
+        if !tokens.is_empty() {
+            return Err(AocError::parse("", "Unexpected text after packet"));
+        }
+        Ok(packet)
     }
 }
 
+/// Parse blank-line-separated pairs of packets.
+pub fn parse_pairs(contents: &str) -> AocResult<Vec<(Packet, Packet)>> {
+    let mut pairs = vec![];
+    // Each pair is two lines plus a blank one.
+    for (i, block) in contents.trim_end().split("\n\n").enumerate() {
+        let first_line = i * 3 + 1;
+        let lines: Vec<_> = block.split('\n').collect();
+        if lines.len() != 2 {
+            return Err(AocError::parse(block, "Expected a pair of packets").at(first_line, 1));
+        }
+        let left_packet = Packet::build_from_text(lines[0])
+            .map_err(|e| e.on_line(first_line, lines[0]))?;
+        let right_packet = Packet::build_from_text(lines[1])
+            .map_err(|e| e.on_line(first_line + 1, lines[1]))?;
+        pairs.push((left_packet, right_packet));
+    }
+    Ok(pairs)
+}
+
 impl Ord for Packet {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         for i in 0..(self.items.len().max(other.items.len())) {
@@ -71,7 +98,7 @@ impl PartialOrd for Packet {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PacketItem {
     Number(i32),
     Packet(Packet),
@@ -129,6 +156,8 @@ impl Display for Packet {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
 
     #[test]
     fn test_display() {
@@ -201,4 +230,23 @@ mod tests {
         let p2 = Packet::build_from_text("[[]]").unwrap();
         assert!(p1 > p2);
     }
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let item = (0..100).prop_map(PacketItem::Number).prop_recursive(4, 32, 4, |inner| {
+            vec(inner, 0..4).prop_map(|items| PacketItem::Packet(Packet::build_from_items(items)))
+        });
+        vec(item, 0..5).prop_map(Packet::build_from_items)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_display_round_trips(packet in packet()) {
+            prop_assert_eq!(Packet::build_from_text(&packet.to_string()), Ok(packet));
+        }
+
+        #[test]
+        fn prop_parse_never_panics(text in "[\\[\\],0-9 x]{0,30}") {
+            let _ = Packet::build_from_text(&text);
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use std::cmp::Ordering;
+
+use common::{AocError, AocResult, Answer, Part};
+
+enum Value {
+    Integer(u32),
+    List(Vec<Value>),
+}
+
+/// Parse one value from the front of `text`, returning it with what's left.
+fn parse(text: &str) -> AocResult<(Value, &str)> {
+    if let Some(mut rest) = text.strip_prefix('[') {
+        let mut items = Vec::new();
+        if let Some(rest) = rest.strip_prefix(']') {
+            return Ok((Value::List(items), rest));
+        }
+        loop {
+            let (item, after) = parse(rest)?;
+            items.push(item);
+            match after.chars().next() {
+                Some(',') => rest = &after[1..],
+                Some(']') => return Ok((Value::List(items), &after[1..])),
+                _ => return Err(AocError::parse(after, "Expected , or ]")),
+            }
+        }
+    }
+    let digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+    Ok((Value::Integer(text[..digits].parse()?), &text[digits..]))
+}
+
+/// The puzzle's ordering rules, one by one.
+fn compare(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (Value::Integer(l), Value::Integer(r)) => l.cmp(r),
+        (Value::List(l), Value::List(r)) => {
+            for (l, r) in l.iter().zip(r) {
+                let order = compare(l, r);
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+            l.len().cmp(&r.len())
+        }
+        (Value::Integer(l), right) => compare(&Value::List(vec![Value::Integer(*l)]), right),
+        (left, Value::Integer(r)) => compare(left, &Value::List(vec![Value::Integer(*r)])),
+    }
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let packets: Vec<Value> = contents
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| parse(line).map(|(value, _)| value))
+        .collect::<AocResult<_>>()?;
+    match part {
+        Part::A => {
+            let ordered: usize = packets
+                .chunks(2)
+                .enumerate()
+                .filter(|(_, pair)| compare(&pair[0], &pair[1]) == Ordering::Less)
+                .map(|(i, _)| i + 1)
+                .sum();
+            Ok(Answer::from(ordered))
+        }
+        Part::B => {
+            // Each divider's position is one more than the number of packets before it.
+            let dividers = [parse("[[2]]")?.0, parse("[[6]]")?.0];
+            let position = |divider: &Value| {
+                let before = packets.iter().chain(&dividers).filter(|p| compare(p, divider) == Ordering::Less);
+                before.count() + 1
+            };
+            Ok(Answer::from(position(&dividers[0]) * position(&dividers[1])))
+        }
+    }
+}
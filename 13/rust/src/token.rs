@@ -1,3 +1,4 @@
+use common::{AocError, AocResult};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Token {
@@ -8,7 +9,7 @@ pub enum Token {
 }
 
 impl Token {
-    pub fn tokenize(text: &str) -> Result<Vec<Self>, String> {
+    pub fn tokenize(text: &str) -> AocResult<Vec<Self>> {
         let chars: Vec<char> = text.chars().collect();
         let mut tokens = Vec::new();
         let mut pos = 0;
@@ -26,18 +27,20 @@ impl Token {
                     tokens.push(Token::Comma);
                     pos += 1;
                 }
-                c if c.is_digit(10) => {
+                c if c.is_ascii_digit() => {
                     let mut pos_range = (pos, pos + 1);
                     // There could be more than just one digit; consume all of them.
-                    if pos_range.1 < chars.len() && chars[pos_range.1].is_digit(10) {
+                    if pos_range.1 < chars.len() && chars[pos_range.1].is_ascii_digit() {
                         pos_range.1 += 1;
                     }
                     let number_str: String = chars[pos_range.0..pos_range.1].iter().collect();
-                    let number: i32 = number_str.parse().map_err(|_| "Unable to parse number")?;
+                    let number: i32 = number_str.parse().map_err(|_| {
+                        AocError::parse(&number_str, "Unable to parse number").at(0, pos + 1)
+                    })?;
                     tokens.push(Self::Number(number));
                     pos = pos_range.1;
                 }
-                _ => return Err("invalid token".to_string()),
+                c => return Err(AocError::parse(&c.to_string(), "Invalid token").at(0, pos + 1)),
             }
         };
         Ok(tokens)
@@ -54,5 +57,6 @@ mod tests {
         assert_eq!(tokens, vec![Token::LB, Token::Number(1), Token::Comma, Token::Number(2), Token::Comma, Token::Number(3), Token::RB]);
         let tokens = Token::tokenize("2,12][").unwrap();
         assert_eq!(tokens, vec![Token::Number(2), Token::Comma, Token::Number(12), Token::RB, Token::LB]);
+        assert_eq!(Token::tokenize("[1,x]"), Err(AocError::parse("x", "Invalid token").at(0, 4)));
     }
 }
\ No newline at end of file
@@ -1,8 +1,11 @@
+use common::{AocResult, Answer};
+
+use crate::cave::Cave;
 use crate::cave_state::CaveState;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let mut state = CaveState::build_from_text(&contents)?;
-    state.drop_sand_until_in_abyss();
+pub fn main(cave: &Cave) -> AocResult<Answer> {
+    let mut state = CaveState::new(cave.clone())?;
+    state.drop_sand_until_in_abyss()?;
     
-    Ok((state.n_dropped - 1).to_string())
+    Ok(Answer::from(state.n_dropped - 1))
 }
\ No newline at end of file
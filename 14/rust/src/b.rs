@@ -1,9 +1,12 @@
+use common::{AocResult, Answer};
+
+use crate::cave::Cave;
 use crate::cave_state::CaveState;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let mut state = CaveState::build_from_text(&contents)?;
+pub fn main(cave: &Cave) -> AocResult<Answer> {
+    let mut state = CaveState::new(cave.clone())?;
     // Add an additional line of blockers at the bottom of the cave.
     state.add_cave_bottom();
-    state.drop_sand_until_blocked();
-    Ok(state.n_dropped.to_string())
+    state.drop_sand_until_blocked()?;
+    Ok(Answer::from(state.n_dropped))
 }
\ No newline at end of file
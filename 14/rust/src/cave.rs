@@ -1,88 +1,79 @@
-use std::collections::HashSet;
+use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Position {
-    pub x: i32,
-    pub y: i32,
+use common::{parse_lines, AocError, AocResult, Point, SparseGrid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Rock,
+    Sand,
 }
 
-impl Position {
-    fn positions_between(&self, other: &Position) -> Result<HashSet<Position>, String> {
-        if self.x != other.x && self.y != other.y {
-            return Err(format!("Invalid endpoints: {:?}, {:?}", self, other));
-        }
-        let mut positions = HashSet::new();
-        if self.x == other.x {
-            let min_y = std::cmp::min(self.y, other.y);
-            let max_y = std::cmp::max(self.y, other.y);
-            for y in min_y..=max_y {
-                positions.insert(Position { x: self.x, y });
-            }
-            return Ok(positions);
-        } else { // self.y == other.y
-            let min_x = std::cmp::min(self.x, other.x);
-            let max_x = std::cmp::max(self.x, other.x);
-            for x in min_x..=max_x {
-                positions.insert(Position { x, y: self.y });
-            }
-            return Ok(positions);
+impl Tile {
+    pub fn symbol(&self) -> char {
+        match self {
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
         }
     }
 }
 
-#[derive(Debug)]
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+// The longest straight stretch of rock we'll collect the points of. Far longer than any
+// real cave's, but short enough that a path across the whole of i32 can't run us out of
+// memory.
+const MAX_STRETCH: i64 = 1 << 20;
+
+// Every point on the straight line from `start` to `end`, inclusive.
+fn positions_between(start: Point, end: Point) -> AocResult<Vec<Point>> {
+    let text = || format!("{} -> {}", start, end);
+    if start.x != end.x && start.y != end.y {
+        return Err(AocError::parse(&text(), "Rock paths must be horizontal or vertical"));
+    }
+    let length = (i64::from(end.x) - i64::from(start.x)).abs() + (i64::from(end.y) - i64::from(start.y)).abs();
+    if length > MAX_STRETCH {
+        return Err(AocError::parse(&text(), "Rock path is too long"));
+    }
+    let step = Point::new(end.x.cmp(&start.x) as i32, end.y.cmp(&start.y) as i32);
+    let mut positions = vec![start];
+    let mut p = start;
+    while p != end {
+        p += step;
+        positions.push(p);
+    }
+    Ok(positions)
+}
+
+#[derive(Debug, Clone)]
 pub struct Cave {
-    pub blockers: HashSet<Position>,
+    pub blockers: SparseGrid<Tile>,
 }
 
 impl Cave {
-    pub fn build_from_text(text: &str) -> Result<Self, String> {
-        let lines = text.lines();
-        let mut blockers = HashSet::new();
-        for line in lines {
+    pub fn build_from_text(text: &str) -> AocResult<Self> {
+        let paths = parse_lines(text, |line| {
             let endpoints = Self::parse_line(line)?;
-            for i in 0..endpoints.len() - 1 {
-                let new_blockers = endpoints[i].positions_between(&endpoints[i + 1])?;
-                blockers.extend(new_blockers);
+            let mut blockers = Vec::new();
+            for pair in endpoints.windows(2) {
+                blockers.extend(positions_between(pair[0], pair[1])?);
             }
-        }
+            Ok(blockers)
+        })?;
+        let blockers = paths.into_iter().flatten().map(|p| (p, Tile::Rock)).collect();
         Ok(Cave{ blockers })
     }
 
-    fn parse_line(line: &str) -> Result<Vec<Position>, String> {
-        let position_strs: Vec<Position> = line
-            .split(" -> ")
-            .into_iter()
-            .map(|s| s.trim())
-            .map(|s| {
-                let parts: Vec<i32> = s.split(',')
-                    .map(|s| {
-                        s
-                            .parse::<i32>()
-                            .map_err(|e| e.to_string())
-                    })
-                    .collect::<Result<Vec<i32>, String>>()?;
-                if parts.len() != 2 {
-                    return Err(format!("Invalid position: {}", s));
-                }
-                Ok(Position { x: parts[0], y: parts[1] })
-            })
-            .collect::<Result<Vec<Position>, String>>()?;
-        Ok(position_strs)
+    fn parse_line(line: &str) -> AocResult<Vec<Point>> {
+        line.split(" -> ").map(|s| s.trim().parse::<Point>()).collect()
     }
 
     pub fn lowest_y(&self) -> Option<i32> {
-        let mut lowest: Option<i32> = None;
-        for blocker in &self.blockers {
-            if let Some(lowest_y) = lowest {
-                if blocker.y > lowest_y {
-                    lowest = Some(blocker.y);
-                }
-            } else {
-                lowest = Some(blocker.y);
-            }
-        }
-        lowest
+        // y grows downwards, so the lowest rock has the greatest y.
+        self.blockers.bounds().map(|(_, max)| max.y)
     }
 }
 
@@ -94,14 +85,12 @@ mod tests {
     fn test_build_from_text_single_line() {
         let cave = Cave::build_from_text("498,4 -> 498,6 -> 496,6").unwrap();
         assert_eq!(cave.blockers.len(), 5);
-        for position in vec![
-            Position { x: 498, y: 4 },
-            Position { x: 498, y: 5 },
-            Position { x: 498, y: 6 },
-            Position { x: 497, y: 6 },
-            Position { x: 496, y: 6 },
-        ] {
-            assert!(cave.blockers.contains(&position));
+        for position in [Point { x: 498, y: 4 },
+            Point { x: 498, y: 5 },
+            Point { x: 498, y: 6 },
+            Point { x: 497, y: 6 },
+            Point { x: 496, y: 6 }] {
+            assert!(cave.blockers.contains(position));
         }
     }
 
@@ -112,29 +101,46 @@ mod tests {
         assert_eq!(cave.blockers.len(), 20);
         for position in vec![
             // Line 1
-            Position { x: 498, y: 4 },
-            Position { x: 498, y: 5 },
-            Position { x: 498, y: 6 },
-            Position { x: 497, y: 6 },
-            Position { x: 496, y: 6 },
+            Point { x: 498, y: 4 },
+            Point { x: 498, y: 5 },
+            Point { x: 498, y: 6 },
+            Point { x: 497, y: 6 },
+            Point { x: 496, y: 6 },
             // Line 2
-            Position { x: 503, y: 4 },
-            Position { x: 502, y: 4 },
-            Position { x: 502, y: 5 },
-            Position { x: 502, y: 6 },
-            Position { x: 502, y: 7 },
-            Position { x: 502, y: 8 },
-            Position { x: 502, y: 9 },
-            Position { x: 501, y: 9 },
-            Position { x: 500, y: 9 },
-            Position { x: 499, y: 9 },
-            Position { x: 498, y: 9 },
-            Position { x: 497, y: 9 },
-            Position { x: 496, y: 9 },
-            Position { x: 495, y: 9 },
-            Position { x: 494, y: 9 },
+            Point { x: 503, y: 4 },
+            Point { x: 502, y: 4 },
+            Point { x: 502, y: 5 },
+            Point { x: 502, y: 6 },
+            Point { x: 502, y: 7 },
+            Point { x: 502, y: 8 },
+            Point { x: 502, y: 9 },
+            Point { x: 501, y: 9 },
+            Point { x: 500, y: 9 },
+            Point { x: 499, y: 9 },
+            Point { x: 498, y: 9 },
+            Point { x: 497, y: 9 },
+            Point { x: 496, y: 9 },
+            Point { x: 495, y: 9 },
+            Point { x: 494, y: 9 },
         ] {
-            assert!(cave.blockers.contains(&position));
+            assert!(cave.blockers.contains(position));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_build_from_text_errors() {
+        assert_eq!(
+            Cave::build_from_text("498,4 -> 498,6\n503,4 -> 50x,4").err(),
+            Some(AocError::parse("50x", "invalid digit found in string").at(2, 10))
+        );
+        assert_eq!(
+            Cave::build_from_text("498,4 -> 497,6").err(),
+            Some(AocError::parse("498,4 -> 497,6", "Rock paths must be horizontal or vertical").at(1, 1))
+        );
+        // The far end is further away than an i32 reaches.
+        assert_eq!(
+            Cave::build_from_text("-2147483648,4 -> 2147483647,4").err(),
+            Some(AocError::parse("-2147483648,4 -> 2147483647,4", "Rock path is too long").at(1, 1))
+        );
+    }
+}
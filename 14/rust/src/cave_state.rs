@@ -1,96 +1,116 @@
-use std::collections::HashSet;
-use crate::cave::{Cave, Position}; 
+use crate::cave::{Cave, Tile};
+use common::{budget, AocError, AocResult, Point, SparseGrid};
+
+// Where the sand pours in from.
+const SOURCE: Point = Point::new(500, 0);
 
 #[derive(Debug)]
 pub struct CaveState {
-    cave: Cave,
-    sand_at_rest: HashSet<Position>,
-    current_sand: Option<Position>,
+    // Rock and sand at rest.
+    tiles: SparseGrid<Tile>,
+    current_sand: Option<Point>,
     abyss_begins: i32,
     pub in_abyss: i32,
     pub n_dropped: i32,
 }
 
 impl CaveState {
-    pub fn build_from_text(text: &str) -> Result<Self, String> {
-        let cave = Cave::build_from_text(text)?;
-        let sand_at_rest = HashSet::new();
+    pub fn new(cave: Cave) -> AocResult<Self> {
         let current_sand = None;
         let in_abyss = 0;
-        let abyss_begins = cave.lowest_y().ok_or("No blockers in cave")?;
+        let abyss_begins = cave.lowest_y().ok_or(AocError::solve("No rocks in cave"))?;
         let n_dropped = 0;
-        Ok(CaveState { cave, sand_at_rest, current_sand, abyss_begins, in_abyss, n_dropped })
+        Ok(CaveState { tiles: cave.blockers, current_sand, abyss_begins, in_abyss, n_dropped })
     }
 
     pub fn drop_sand(&mut self) {
-        self.current_sand = Some(Position { x: 500, y: 0 });
+        self.current_sand = Some(SOURCE);
         self.n_dropped += 1;
         while self.update() {}
+        log::trace!("After {} units of sand:\n{}", self.n_dropped, self);
     }
 
-    pub fn drop_sand_until_in_abyss(&mut self) {
+    pub fn drop_sand_until_in_abyss(&mut self) -> AocResult<()> {
         while self.in_abyss == 0 {
+            budget::checkpoint()?;
             self.drop_sand();
         }
+        Ok(())
     }
 
-    pub fn drop_sand_until_blocked(&mut self) {
-        while !self.sand_at_rest.contains(&Position{x: 500, y: 0}) {
+    pub fn drop_sand_until_blocked(&mut self) -> AocResult<()> {
+        while !self.tiles.contains(SOURCE) {
+            budget::checkpoint()?;
             self.drop_sand();
         }
+        Ok(())
     }
 
     // Move one piece of sand down one step. Return false if there is no sand to move.
     fn update(&mut self) -> bool {
-        if let Some(current_pos) = self.current_sand.take() {
-            match current_pos {
-                // Check if we've fallen past the lowest blocker, in which case we're in the abyss.
-                // By waiting until the sand is 3 squares into the abyss, we let the sand settle on the cave "bottom" if we've added one.
-                Position{ x: _, y } if y > self.abyss_begins + 3 => {
-                    self.in_abyss += 1;
-                    return false;
-                },
-                // Try to fall straight down.
-                Position{ x, y } if !self.is_blocked(Position{ x, y: y + 1 }) => {
-                    self.current_sand = Some(Position{ x, y: y + 1 });
-                    return true;
-                },
-                // Try to fall down and to the left.
-                Position{ x, y } if !self.is_blocked(Position{ x: x - 1, y: y + 1 }) => {
-                    self.current_sand = Some(Position{ x: x - 1, y: y + 1 });
-                    return true;
-                },
-                // Try to fall down and to the right.
-                Position{ x, y } if !self.is_blocked(Position{ x: x + 1, y: y + 1 }) => {
-                    self.current_sand = Some(Position{ x: x + 1, y: y + 1 });
-                    return true;
-                },
-                // If all these are blocked, the sand is at rest.
-                Position{ x, y } => {
-                    self.sand_at_rest.insert(Position{ x, y });
-                    return false;
-                },
-
-            }
-        } else {
+        let Some(current_pos) = self.current_sand.take() else {
             // There is no sand in motion, so we just return false.
-            return false
+            return false;
         };
+        // Check if we've fallen past the lowest blocker, in which case we're in the abyss.
+        // By waiting until the sand is 3 squares into the abyss, we let the sand settle on the cave "bottom" if we've added one.
+        if current_pos.y > self.abyss_begins + 3 {
+            self.in_abyss += 1;
+            return false;
+        }
+        // Try to fall straight down, then down and to the left, then down and to the right.
+        let moves = [Point::DOWN, Point::DOWN + Point::LEFT, Point::DOWN + Point::RIGHT];
+        match moves.into_iter().map(|m| current_pos + m).find(|p| !self.is_blocked(*p)) {
+            Some(next) => {
+                self.current_sand = Some(next);
+                true
+            }
+            // If all these are blocked, the sand is at rest.
+            None => {
+                self.tiles.insert(current_pos, Tile::Sand);
+                false
+            }
+        }
     }
 
-    fn is_blocked(&self, p: Position) -> bool {
-        self.cave.blockers.contains(&p) || self.sand_at_rest.contains(&p)
+    fn is_blocked(&self, p: Point) -> bool {
+        self.tiles.contains(p)
     }
 
     pub fn add_cave_bottom(&mut self) {
         // Add an additional line of blockers at the bottom of the cave.
-        let lowest_y = self.cave.lowest_y().unwrap();
-        let min_x = self.cave.blockers.iter().map(|p| p.x).min().unwrap();
-        let max_x = self.cave.blockers.iter().map(|p| p.x).max().unwrap();
+        let (min, max) = self.tiles.bounds().unwrap();
+        let lowest_y = max.y;
         // Add blockers all along the bottom, with enough extra margin on each side to
         // keep things from ever falling off the sides.
-        for x in (min_x - lowest_y - 1)..(max_x + lowest_y + 1) {
-            self.cave.blockers.insert(Position { x, y: lowest_y + 2 });
+        for x in (min.x - lowest_y - 1)..(max.x + lowest_y + 1) {
+            self.tiles.insert(Point::new(x, lowest_y + 2), Tile::Rock);
         }
     }
-}
\ No newline at end of file
+}
+
+impl std::fmt::Display for CaveState {
+    /// Draw the cave as in the puzzle: `#` for rock, `o` for sand at rest and `+` for the
+    /// source. The cave floor, if there is one, is left out so the drawing stays narrow.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let floor = self.abyss_begins + 2;
+        let visible: SparseGrid<Tile> = self
+            .tiles
+            .iter()
+            .filter(|(p, _)| p.y < floor)
+            .map(|(p, tile)| (p, *tile))
+            .collect();
+        let (min, max) = visible.bounds().unwrap_or((SOURCE, SOURCE));
+        // Make sure the source is in the picture.
+        let bounds = (
+            Point::new(min.x.min(SOURCE.x), min.y.min(SOURCE.y)),
+            Point::new(max.x.max(SOURCE.x), max.y),
+        );
+        let drawing = visible.render(bounds, |p, tile| match tile {
+            Some(tile) => tile.symbol(),
+            None if p == SOURCE => '+',
+            None => '.',
+        });
+        write!(f, "{}", drawing)
+    }
+}
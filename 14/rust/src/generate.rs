@@ -0,0 +1,36 @@
+use common::{Generator, Rng};
+
+use crate::Day14;
+
+/// `size` paths of rock, each a few straight horizontal or vertical lines below the source
+/// of the sand at 500,0. The first starts with a ledge right under the source, so some
+/// sand comes to rest before the floor.
+impl Generator for Day14 {
+    const DEFAULT_SIZE: usize = 150;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1))
+            .map(|i| {
+                let (mut x, mut y) = (rng.range(450..=550), rng.range(2..=160));
+                let mut horizontal = rng.chance(1, 2);
+                if i == 0 {
+                    x = rng.range(490..=499);
+                    horizontal = true;
+                }
+                let mut points = vec![format!("{},{}", x, y)];
+                for _ in 0..rng.range(1..=4) {
+                    if i == 0 && points.len() == 1 {
+                        x = rng.range(501..=510);
+                    } else if horizontal {
+                        x = (x + rng.range(-6..=6)).clamp(450, 550);
+                    } else {
+                        y = (y + rng.range(-6..=6)).clamp(2, 160);
+                    }
+                    points.push(format!("{},{}", x, y));
+                    horizontal = !horizontal;
+                }
+                points.join(" -> ") + "\n"
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer, Part};
+
+const SOURCE: (i32, i32) = (500, 0);
+
+fn rocks(contents: &str) -> AocResult<HashSet<(i32, i32)>> {
+    let mut rocks = HashSet::new();
+    for line in contents.lines() {
+        let points: Vec<(i32, i32)> = line
+            .split(" -> ")
+            .map(|point| {
+                let (x, y) = point
+                    .split_once(',')
+                    .ok_or_else(|| AocError::parse(point, "Expected x,y"))?;
+                Ok((x.parse()?, y.parse()?))
+            })
+            .collect::<AocResult<_>>()?;
+        for pair in points.windows(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            for x in x1.min(x2)..=x1.max(x2) {
+                for y in y1.min(y2)..=y1.max(y2) {
+                    rocks.insert((x, y));
+                }
+            }
+        }
+    }
+    Ok(rocks)
+}
+
+/// Drop sand one unit at a time, each falling a square at a time, and count how many come
+/// to rest: before one falls past the lowest rock (part A), or until the source is buried
+/// (part B, with a floor two below the lowest rock).
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let mut blocked = rocks(contents)?;
+    let lowest = blocked.iter().map(|(_, y)| *y).max().ok_or(AocError::NoAnswer)?;
+    let floor = lowest + 2;
+    let mut resting = 0;
+    while !blocked.contains(&SOURCE) {
+        let (mut x, mut y) = SOURCE;
+        loop {
+            if part == Part::A && y > lowest {
+                return Ok(Answer::from(resting));
+            }
+            let next = [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+                .into_iter()
+                .find(|square| square.1 < floor && !blocked.contains(square));
+            match next {
+                Some(square) => (x, y) = square,
+                None => break,
+            }
+        }
+        blocked.insert((x, y));
+        resting += 1;
+    }
+    Ok(Answer::from(resting))
+}
@@ -0,0 +1,49 @@
+use common::{Generator, Rng};
+
+use crate::Day15;
+
+const MAX_COORD: i64 = 4_000_000;
+/// Far enough from anywhere in the search area that one of the surrounding sensors reaches
+/// every position in it but the hidden one.
+const FAR: i64 = MAX_COORD + 1;
+
+/// `size` sensors (at least 8). Eight sensors far outside the search area reach all of it
+/// but one hidden position, and the rest are scattered inside it, all out of range of that
+/// position, so exactly one place is left for the distress beacon.
+impl Generator for Day15 {
+    const DEFAULT_SIZE: usize = 30;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let hidden = (rng.range(0..=MAX_COORD), rng.range(0..=MAX_COORD));
+        let mut sensors = Vec::new();
+        // Straight out from the hidden position, each reaching up to just before it...
+        for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            let sensor = (hidden.0 + dx * FAR, hidden.1 + dy * FAR);
+            sensors.push((sensor, (hidden.0 + dx, hidden.1 + dy)));
+        }
+        // ...and diagonally, for the positions on the diagonals through it.
+        for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let sensor = (hidden.0 + dx * FAR, hidden.1 + dy * FAR);
+            sensors.push((sensor, (hidden.0 + dx, hidden.1)));
+        }
+        while sensors.len() < size {
+            let sensor = (rng.range(0..=MAX_COORD), rng.range(0..=MAX_COORD));
+            let distance = (sensor.0 - hidden.0).abs() + (sensor.1 - hidden.1).abs();
+            if distance == 0 {
+                continue;
+            }
+            let reach = rng.range(0..=(distance - 1).min(MAX_COORD / 4));
+            let dx = rng.range(0..=reach);
+            let (sx, sy) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+            sensors.push((sensor, (sensor.0 + sx * dx, sensor.1 + sy * (reach - dx))));
+        }
+        rng.shuffle(&mut sensors);
+
+        sensors
+            .into_iter()
+            .map(|((sx, sy), (bx, by))| {
+                format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n", sx, sy, bx, by)
+            })
+            .collect()
+    }
+}
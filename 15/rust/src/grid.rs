@@ -1,26 +1,23 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 
+use common::{parse_lines, AocError, AocResult, Point, SparseGrid};
 use regex::Regex;
 
-pub fn manhattan(p1: &Position, p2: &Position) -> i32 {
-    (p1.x - p2.x).abs() + (p1.y - p2.y).abs()
-}
-
 pub struct Grid {
     pub readings: Vec<Reading>,
 }
 
 impl Grid {
-    fn sensors(&self) -> HashSet<&Position> {
+    fn sensors(&self) -> HashSet<&Point> {
         self.readings.iter().map(|r| &r.sensor).collect()
     }
 
-    fn beacons(&self) -> HashSet<&Position> {
+    fn beacons(&self) -> HashSet<&Point> {
         self.readings.iter().map(|r| &r.beacon).collect()
     }
 
-    fn min_and_max_coords(&self) -> (Position, Position) {
+    fn min_and_max_coords(&self) -> (Point, Point) {
         let readings = &self.readings;
         let mut min_x = 0;
         let mut max_x = 0;
@@ -44,7 +41,7 @@ impl Grid {
                 max_y = r_max_y;
             }
         }
-        (Position{ x: min_x, y: min_y }, Position{ x: max_x, y: max_y })
+        (Point{ x: min_x, y: min_y }, Point{ x: max_x, y: max_y })
     }
 
     fn greatest_reading_distance(&self) -> i32 {
@@ -56,15 +53,15 @@ impl Grid {
         // Start at the left-most point plus the greatest distance of any reading, and go to the
         // right-most point plus the greatest distance of any reading.
         // For each point, check if it's excluded based on being near enough to a sensor or beacon.
-        let (Position { x: min_x, .. }, Position{ x: max_x, .. }) = self.min_and_max_coords();
+        let (Point { x: min_x, .. }, Point{ x: max_x, .. }) = self.min_and_max_coords();
         let max_distance = self.greatest_reading_distance();
         let min_x = min_x - max_distance;
         let max_x = max_x + max_distance;
         let mut excluded = vec![];
         'x_loop: for x in min_x..=max_x {
-            let position = Position{ x, y };
+            let position = Point{ x, y };
             for r in &self.readings {
-                if manhattan(&position, &r.sensor) <= r.distance {
+                if position.manhattan(r.sensor) <= r.distance {
                     // This position is within range of a sensor, so it's excluded.
                     excluded.push(position);
                     continue 'x_loop;
@@ -72,165 +69,132 @@ impl Grid {
             }
         }
         // Go back through and remove positions that are occupied by a sensor or beacon.
-        let objects = self.sensors().into_iter().chain(self.beacons().into_iter()).collect::<HashSet<&Position>>();
-        excluded = excluded.into_iter().filter(|e| !objects.contains(e)).collect();
+        let objects = self.sensors().into_iter().chain(self.beacons()).collect::<HashSet<&Point>>();
+        excluded.retain(|e| !objects.contains(e));
         excluded.len() as i32
     }
 }
 
 impl Display for Grid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let sensors = self.sensors();
-        let beacons = self.beacons();
-        let excluded = self.excluded_positions();
-        let (Position { x: min_x, y: min_y }, Position { x: max_x, y: max_y }) =
-            self.min_and_max_coords();
+        // Sensors are drawn over beacons, which are drawn over excluded positions.
+        let mut map: SparseGrid<char> =
+            self.excluded_positions().into_iter().map(|p| (p, '#')).collect();
+        map.extend(self.beacons().into_iter().map(|p| (*p, 'B')));
+        map.extend(self.sensors().into_iter().map(|p| (*p, 'S')));
+        let (min, max) = self.min_and_max_coords();
         // Print our numbers across the top before starting the loop proper.
         write!(f, "        ")?;
-        for x in min_x..=max_y {
-            if x <= 9 && x >= 0 {
+        for x in min.x..=max.x {
+            if (0..=9).contains(&x) {
                 write!(f, "{}", x)?;
             } else {
                 write!(f, " ")?;
             }
         }
-        write!(f, "\n")?;
-        for y in min_y..=max_y {
-            write!(f, "{: >8}", y)?;
-            for x in min_x..=max_x {
-                let position = Position { x, y };
-                let sensor = sensors.contains(&position);
-                let beacon = beacons.contains(&position);
-                let excluded = excluded.contains(&position);
-                if sensor {
-                    write!(f, "S")?;
-                } else if beacon {
-                    write!(f, "B")?;
-                } else if excluded {
-                    write!(f, "#")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-            write!(f, "\n")?;
+        writeln!(f)?;
+        let drawing = map.render((min, max), |_, c| c.copied().unwrap_or('.'));
+        for (y, row) in (min.y..=max.y).zip(drawing.lines()) {
+            writeln!(f, "{: >8}{}", y, row)?;
         }
-
         Ok(())
     }
 }
 
 impl Grid {
-    pub fn build_from_text(text: &str) -> Result<Grid, String> {
-        println!("Parsing...");
-        let mut readings = Vec::new();
-        for line in text.lines() {
-            let reading = Reading::build_from_line(line)?;
-            readings.push(reading);
+    pub fn build_from_text(text: &str) -> AocResult<Grid> {
+        let readings = parse_lines(text, Reading::build_from_line)?;
+        if readings.is_empty() {
+            return Err(AocError::parse("", "No readings"));
         }
-        println!("Done parsing.");
+        log::debug!("Parsed {} readings", readings.len());
         Ok(Grid { readings })
     }
 
-    fn excluded_positions(&self) -> HashSet<Position> {
-        println!("Grid.excluded_positions ->");
-        let e = self
+    fn excluded_positions(&self) -> HashSet<Point> {
+        let excluded: HashSet<Point> = self
             .readings
             .iter()
-            .map(|r| r.excluded_positions())
-            .flatten()
+            .flat_map(|r| r.excluded_positions())
             .collect();
-        println!("Done");
-        e
+        log::debug!("{} positions excluded in total", excluded.len());
+        excluded
     }
     
 }
 
 #[derive(Debug)]
 pub struct Reading {
-    pub sensor: Position,
+    pub sensor: Point,
     // The closest beacon to this sensor.
-    beacon: Position,
+    beacon: Point,
     // The manhattan distance between them.
     pub distance: i32,
 }
 
 impl Reading {
-    pub fn new(sensor: Position, beacon: Position) -> Reading {
-        let distance = manhattan(&sensor, &beacon);
-        Reading {
+    /// Fails if the sensor's range, and the row of positions just outside it, don't fit
+    /// in an `i32`: the solvers walk along both.
+    pub fn new(sensor: Point, beacon: Point) -> AocResult<Reading> {
+        let (x, y) = (i64::from(sensor.x), i64::from(sensor.y));
+        let distance = (x - i64::from(beacon.x)).abs() + (y - i64::from(beacon.y)).abs();
+        let reach = distance + 1;
+        if [x - reach, x + reach, y - reach, y + reach].into_iter().any(|n| i32::try_from(n).is_err()) {
+            return Err(AocError::parse("", "The sensor's range is too big"));
+        }
+        Ok(Reading {
             sensor,
             beacon,
-            distance,
-        }
+            distance: distance as i32,
+        })
     }
 
     // Get all the positions that we can rule out as having a beacon.
-    pub fn excluded_positions(&self) -> HashSet<Position> {
-        println!("Reading.excluded_positions ->");
+    pub fn excluded_positions(&self) -> HashSet<Point> {
         let mut excluded = HashSet::new();
         let x_range = self.sensor.x - self.distance..=self.sensor.x + self.distance;
         let y_range = self.sensor.y - self.distance..=self.sensor.y + self.distance;
         for x in x_range {
             for y in y_range.clone() {
-                let p = Position { x, y };
-                if manhattan(&p, &self.sensor) <= self.distance {
-                    excluded.insert(Position { x, y });
+                let p = Point { x, y };
+                if p.manhattan(self.sensor) <= self.distance {
+                    excluded.insert(Point { x, y });
                 }
             }
         }
-        println!("Done.");
+        log::trace!("Sensor at {:?} excludes {} positions", self.sensor, excluded.len());
         excluded
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
-pub struct Position {
-    pub x: i32,
-    pub y: i32,
-}
-
 impl Reading {
-    pub fn build_from_line(text: &str) -> Result<Reading, String> {
+    pub fn build_from_line(text: &str) -> AocResult<Reading> {
         // Readings look like: "Sensor at x=2, y=18: closest beacon is at x=-2, y=15"
         let text = text.trim();
         let re = Regex::new(r"x=(-?[\d]+), y=(-?[\d]+).*x=([-?\d]+), y=(-?[\d]+)").unwrap();
         let captures = re
             .captures(text)
-            .ok_or(format!("didn't match text: '{}'", text))?;
-        let sensor_x = captures
-            .get(1)
-            .unwrap()
-            .as_str()
-            .parse::<i32>()
-            .map_err(|_| "bad parse")?;
-        let sensor_y = captures
-            .get(2)
-            .unwrap()
-            .as_str()
-            .parse::<i32>()
-            .map_err(|_| "bad parse")?;
-        let beacon_x = captures
-            .get(3)
-            .unwrap()
-            .as_str()
-            .parse::<i32>()
-            .map_err(|_| "bad parse")?;
-        let beacon_y = captures
-            .get(4)
-            .unwrap()
-            .as_str()
-            .parse::<i32>()
-            .map_err(|_| "bad parse")?;
-        Ok(Reading::new(
-            Position {
+            .ok_or(AocError::parse("", "Expected a sensor and a beacon position"))?;
+        let coordinate = |i: usize| -> AocResult<i32> {
+            let m = captures.get(i).unwrap();
+            m.as_str()
+                .parse::<i32>()
+                .map_err(|e| AocError::parse(m.as_str(), &e.to_string()).at(0, m.start() + 1))
+        };
+        let sensor_x = coordinate(1)?;
+        let sensor_y = coordinate(2)?;
+        let beacon_x = coordinate(3)?;
+        let beacon_y = coordinate(4)?;
+        Reading::new(
+            Point {
                 x: sensor_x,
                 y: sensor_y,
             },
-            Position {
+            Point {
                 x: beacon_x,
                 y: beacon_y,
             },
-        ))
+        )
     }
 }
 
@@ -258,6 +222,37 @@ mod tests {
         assert_eq!(26, grid.excluded_count_in_row(10));
     }
 
+    #[test]
+    fn test_build_from_text_errors() {
+        let text = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=99999999999";
+        assert_eq!(
+            Grid::build_from_text(text).err(),
+            Some(AocError::parse("99999999999", "number too large to fit in target type").at(2, 51))
+        );
+        assert!(Grid::build_from_text("Sensor at x=2").is_err());
+        // Each coordinate fits in an i32, but the distance between them doesn't.
+        let text = "Sensor at x=2147483647, y=0: closest beacon is at x=-2147483648, y=0";
+        assert_eq!(
+            Grid::build_from_text(text).err(),
+            Some(AocError::parse(text, "The sensor's range is too big").at(1, 1))
+        );
+        // Nor does the edge of a range that reaches the largest coordinate.
+        assert!(Grid::build_from_text("Sensor at x=0, y=2147483646: closest beacon is at x=0, y=2147483645").is_err());
+        assert!(Grid::build_from_text("Sensor at x=0, y=2147483645: closest beacon is at x=0, y=2147483644").is_ok());
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::build_from_text("Sensor at x=0, y=0: closest beacon is at x=1, y=0").unwrap();
+        let expected = "         01
+      -1.#.
+       0#SB
+       1.#.
+";
+        assert_eq!(grid.to_string(), expected);
+    }
+
     #[test]
     fn test_min_and_max_coords_fast() {
         let text = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
@@ -276,7 +271,7 @@ mod tests {
         Sensor at x=20, y=1: closest beacon is at x=15, y=3";
         let grid = Grid::build_from_text(text).unwrap();
         let (p1, p2) = grid.min_and_max_coords();
-        assert_eq!(Position { x: -8, y: -10 }, p1);
-        assert_eq!(Position { x: 28, y: 26 }, p2);
+        assert_eq!(Point { x: -8, y: -10 }, p1);
+        assert_eq!(Point { x: 28, y: 26 }, p2);
     }
 }
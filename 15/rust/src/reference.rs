@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer, Part};
+
+const ROW_OF_INTEREST: i64 = 2_000_000;
+const MAX_COORD: i64 = 4_000_000;
+
+struct Sensor {
+    x: i64,
+    y: i64,
+    beacon: (i64, i64),
+    range: i64,
+}
+
+impl Sensor {
+    /// The columns this sensor reaches in row `y`, if any.
+    fn reach_in_row(&self, y: i64) -> Option<(i64, i64)> {
+        let spare = self.range - (self.y - y).abs();
+        (spare >= 0).then_some((self.x - spare, self.x + spare))
+    }
+}
+
+fn parse(contents: &str) -> AocResult<Vec<Sensor>> {
+    let mut sensors = Vec::new();
+    for line in contents.lines() {
+        let numbers: Vec<i64> = line
+            .split(|c: char| c != '-' && !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<i64>())
+            .collect::<Result<_, _>>()?;
+        let [x, y, bx, by] = numbers[..] else {
+            return Err(AocError::parse(line, "Expected a sensor and a beacon"));
+        };
+        sensors.push(Sensor { x, y, beacon: (bx, by), range: (x - bx).abs() + (y - by).abs() });
+    }
+    Ok(sensors)
+}
+
+/// Row by row, walking along each row by jumping past whichever sensor reaches the current
+/// column; a column no sensor reaches is the distress beacon.
+fn find_distress_beacon(sensors: &[Sensor]) -> Option<(i64, i64)> {
+    for y in 0..=MAX_COORD {
+        let mut x = 0;
+        while x <= MAX_COORD {
+            let reach = sensors
+                .iter()
+                .filter_map(|sensor| sensor.reach_in_row(y))
+                .find(|(start, end)| (*start..=*end).contains(&x));
+            match reach {
+                Some((_, end)) => x = end + 1,
+                None => return Some((x, y)),
+            }
+        }
+    }
+    None
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let sensors = parse(contents)?;
+    match part {
+        Part::A => {
+            // Every column some sensor reaches, except where there's a beacon.
+            let mut reached: Vec<(i64, i64)> =
+                sensors.iter().filter_map(|s| s.reach_in_row(ROW_OF_INTEREST)).collect();
+            reached.sort();
+            let mut count = 0;
+            let mut next = i64::MIN;
+            for (start, end) in reached {
+                let start = start.max(next);
+                if end >= start {
+                    count += end - start + 1;
+                    next = end + 1;
+                }
+            }
+            let beacons: HashSet<(i64, i64)> =
+                sensors.iter().map(|s| s.beacon).filter(|(_, y)| *y == ROW_OF_INTEREST).collect();
+            Ok(Answer::from(count - beacons.len() as i64))
+        }
+        Part::B => {
+            let (x, y) = find_distress_beacon(&sensors).ok_or(AocError::NoAnswer)?;
+            Ok(Answer::from(x * 4_000_000 + y))
+        }
+    }
+}
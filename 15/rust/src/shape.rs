@@ -1,53 +1,55 @@
-use crate::grid::{Grid, Reading, Position, manhattan};
+use common::Point;
+
+use crate::grid::{Grid, Reading};
 
 #[derive(Debug)]
 pub struct Diamond {
-    center: Position,
+    center: Point,
     radius: i32,
 }
 
 impl Diamond {
-    fn build_from_reading(r: Reading) -> Diamond {
+    fn build_from_reading(r: &Reading) -> Diamond {
         Diamond { center: r.sensor, radius: r.distance}
     }
 
-    pub fn build_from_grid(g: Grid) -> Vec<Diamond> {
+    pub fn build_from_grid(g: &Grid) -> Vec<Diamond> {
         let mut diamonds: Vec<Diamond> = Vec::new();
-        for r in g.readings {
+        for r in &g.readings {
             diamonds.push(Diamond::build_from_reading(r));
         }
         diamonds
     }
 
-    pub fn contains(&self, p: &Position) -> bool {
+    pub fn contains(&self, p: &Point) -> bool {
         // Returns true if the given point falls within the diamond.
-        manhattan(p, &self.center) <= self.radius
+        p.manhattan(self.center) <= self.radius
     }
 
     pub fn frame(&self) -> Vec<Line> {
         // Returns lines that frame the diamond but don't overlap with its edges.
-        let mut lines = Vec::new();
-        // Top left corner
-        lines.push(Line::new(
-            Position{x: self.center.x - self.radius - 1, y: self.center.y},
-            Position{x: self.center.x, y: self.center.y + self.radius + 1},
-        ));
-        // Top right corner
-        lines.push(Line::new(
-            Position{x: self.center.x, y: self.center.y + self.radius + 1},
-            Position{x: self.center.x + self.radius + 1, y: self.center.y},
-        ));
-        // Bottom right corner
-        lines.push(Line::new(
-            Position{x: self.center.x + self.radius + 1, y: self.center.y},
-            Position{x: self.center.x, y: self.center.y - self.radius - 1},
-        ));
-        // Bottom left corner
-        lines.push(Line::new(
-            Position{x: self.center.x, y: self.center.y - self.radius - 1},
-            Position{x: self.center.x - self.radius - 1, y: self.center.y},
-        ));
-        lines
+        vec![
+            // Top left corner
+            Line::new(
+                Point{x: self.center.x - self.radius - 1, y: self.center.y},
+                Point{x: self.center.x, y: self.center.y + self.radius + 1},
+            ),
+            // Top right corner
+            Line::new(
+                Point{x: self.center.x, y: self.center.y + self.radius + 1},
+                Point{x: self.center.x + self.radius + 1, y: self.center.y},
+            ),
+            // Bottom right corner
+            Line::new(
+                Point{x: self.center.x + self.radius + 1, y: self.center.y},
+                Point{x: self.center.x, y: self.center.y - self.radius - 1},
+            ),
+            // Bottom left corner
+            Line::new(
+                Point{x: self.center.x, y: self.center.y - self.radius - 1},
+                Point{x: self.center.x - self.radius - 1, y: self.center.y},
+            ),
+        ]
     }
 }
 
@@ -73,7 +75,7 @@ impl Square {
         Square { x1, y1, x2, y2 }
     }
 
-    pub fn contains(&self, p: &Position) -> bool {
+    pub fn contains(&self, p: &Point) -> bool {
         // Returns true if the given point falls within the square.
         p.x >= self.x1 && p.x <= self.x2 && p.y >= self.y1 && p.y <= self.y2
     }
@@ -81,35 +83,36 @@ impl Square {
 
 #[derive(Debug)]
 pub struct Line {
-    p1: Position,
-    p2: Position,
+    p1: Point,
+    p2: Point,
 }
 
 impl Line {
-    pub fn new(p1: Position, p2: Position) -> Line {
+    pub fn new(p1: Point, p2: Point) -> Line {
         Line { p1, p2 }
     }
 
-    pub fn intersection(&self, other: &Line) -> Option<Position> {
+    pub fn intersection(&self, other: &Line) -> Option<Point> {
         // Returns the point at which the two lines intersect, if any.
-        // Based on https://stackoverflow.com/a/565282/120898
-        let x1 = self.p1.x as f64;
-        let y1 = self.p1.y as f64;
-        let x2 = self.p2.x as f64;
-        let y2 = self.p2.y as f64;
-        let x3 = other.p1.x as f64;
-        let y3 = other.p1.y as f64;
-        let x4 = other.p2.x as f64;
-        let y4 = other.p2.y as f64;
+        // Based on https://stackoverflow.com/a/565282/120898, in integers: the products
+        // outgrow an f64's precision with coordinates in the millions.
+        let x1 = self.p1.x as i128;
+        let y1 = self.p1.y as i128;
+        let x2 = self.p2.x as i128;
+        let y2 = self.p2.y as i128;
+        let x3 = other.p1.x as i128;
+        let y3 = other.p1.y as i128;
+        let x4 = other.p2.x as i128;
+        let y4 = other.p2.y as i128;
         let denom = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
         // If denom is 0, the lines are parallel. I'm hoping that we don't have to worry
         // about this case.
-        if denom == 0.0 {
+        if denom == 0 {
             return None;
         }
         let x = ((x1 * y2 - y1 * x2) * (x3 - x4) - (x1 - x2) * (x3 * y4 - y3 * x4)) / denom;
         let y = ((x1 * y2 - y1 * x2) * (y3 - y4) - (y1 - y2) * (x3 * y4 - y3 * x4)) / denom;
-        Some(Position{x: x as i32, y: y as i32})
+        Some(Point{x: x as i32, y: y as i32})
     }
 }
 
@@ -119,14 +122,20 @@ mod tests {
 
     #[test]
     fn test_intersection() {
-        let l1 = Line::new(Position{x: 0, y: 0}, Position{x: 2, y: 2});
-        let l2 = Line::new(Position{x: 0, y: 2}, Position{x: 2, y: 0});
+        let l1 = Line::new(Point{x: 0, y: 0}, Point{x: 2, y: 2});
+        let l2 = Line::new(Point{x: 0, y: 2}, Point{x: 2, y: 0});
+        let i = l1.intersection(&l2);
+        assert_eq!(i, Some(Point{x: 1, y: 1}));
+
+        let l1 = Line::new(Point{x: 0, y: 0}, Point{x: 4, y: 4});
+        let l2 = Line::new(Point{x: -10, y: 24}, Point{x: 4, y: 4});
         let i = l1.intersection(&l2);
-        assert_eq!(i, Some(Position{x: 1, y: 1}));
+        assert_eq!(i, Some(Point{x: 4, y: 4}));
 
-        let l1 = Line::new(Position{x: 0, y: 0}, Position{x: 4, y: 4});
-        let l2 = Line::new(Position{x: -10, y: 24}, Position{x: 4, y: 4});
+        // Far enough out that the products lose precision as floats.
+        let l1 = Line::new(Point{x: 1534628, y: 2959533}, Point{x: 5534629, y: 6959534});
+        let l2 = Line::new(Point{x: 5534629, y: -1040468}, Point{x: 1534628, y: 2959533});
         let i = l1.intersection(&l2);
-        assert_eq!(i, Some(Position{x: 4, y: 4}));
+        assert_eq!(i, Some(Point{x: 1534628, y: 2959533}));
     }
 }
\ No newline at end of file
@@ -1,16 +1,13 @@
+use common::{AocResult, Answer};
+
 use crate::distance_matrix::DistanceMatrix;
 use crate::game_state::GameState;
-use crate::parse;
 use std::rc::Rc;
 
 const STEPS: usize = 26;
 
-pub fn main(contents: String) -> Result<String, String> {
-    let valves = parse::parse(contents)?;
-    let distance_matrix = DistanceMatrix::new(valves);
-    let distance_matrix = distance_matrix.with_valves_removed();
-
-    let state = GameState::new(2, Rc::new(distance_matrix), STEPS);
-    let answer = state.maximize_flow();
-    Ok(answer.to_string())
+pub fn main(distance_matrix: &DistanceMatrix) -> AocResult<Answer> {
+    let state = GameState::new(2, Rc::new(distance_matrix.clone()), STEPS);
+    let answer = state.maximize_flow()?;
+    Ok(Answer::from(answer))
 }
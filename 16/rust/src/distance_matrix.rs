@@ -1,10 +1,22 @@
 use crate::valve::Valve;
+use common::graph::{self, Graph};
 use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
 };
 
-const STARTING_VALVE: &str = "AA";
+pub const STARTING_VALVE: &str = "AA";
+
+/// The valves as a graph, with a tunnel taking a minute to walk.
+struct Tunnels<'a>(&'a HashMap<String, Valve>);
+
+impl Graph for Tunnels<'_> {
+    type Node = String;
+
+    fn neighbors(&self, name: &String) -> Vec<String> {
+        self.0[name].linked_valves.clone()
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DistanceMatrix {
@@ -22,38 +34,17 @@ impl DistanceMatrix {
         valves: &HashMap<String, Valve>,
     ) -> HashMap<String, HashMap<String, usize>> {
         // e.g. matrix[a][b] = 3 means that fastest route from a to b is 3
-        let mut distance_matrix: HashMap<String, HashMap<String, usize>> = HashMap::new();
-        let all_valve_names: Vec<String> = valves.keys().map(|s| s.clone()).collect();
-        // Find the shortest path from each valve to each other valve.
-        for start in &all_valve_names {
-            // Start having seen only the start node. Track the distance from the start node to each.
-            let mut seen: HashMap<String, usize> = HashMap::new();
-            let mut distance = 0;
-            seen.insert(start.clone(), distance);
-            // Loop until we've seen every endpoint (excluding the start).
-            while seen.len() < all_valve_names.len() {
-                distance += 1;
-                // For each node we've seen, find all the nodes we haven't seen that it's connected to.
-                let seen_copy = seen.clone();
-                let seen_to_this_point: Vec<&String> = seen_copy.keys().collect();
-                for name in &seen_to_this_point {
-                    let valve = &valves[*name];
-                    valve.linked_valves.iter().for_each(|v| {
-                        if !seen_to_this_point.contains(&v) {
-                            seen.insert(v.to_string(), distance);
-                        }
-                    });
-                }
-            }
-            // Remove the starting node -- it's redundant.
-            seen.remove(start);
-            distance_matrix.insert(start.clone(), seen);
+        let all_valve_names: Vec<String> = valves.keys().cloned().collect();
+        let mut matrix = graph::floyd_warshall(&Tunnels(valves), &all_valve_names);
+        // Remove each valve's distance to itself -- it's redundant.
+        for (start, distances) in matrix.iter_mut() {
+            distances.remove(start);
         }
-        distance_matrix
+        matrix
     }
 
     pub fn distance(&self, start: &str, end: &str) -> Option<usize> {
-        self.matrix.get(start).and_then(|m| m.get(end).map(|d| *d))
+        self.matrix.get(start).and_then(|m| m.get(end).copied())
     }
 
     /// Create a copy of this distance matrix without valves that have 0 flow rate.
@@ -91,6 +82,7 @@ impl DistanceMatrix {
         self.valves[name].rate
     }
 
+    #[allow(dead_code)]
     pub fn paths_from(&self, name: &str) -> HashMap<String, usize> {
         self.matrix[name].clone()
     }
@@ -111,3 +103,29 @@ impl Display for DistanceMatrix {
         write!(f, "{}", output)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let valves = [
+            Valve::new(String::from("AA"), 0, vec!["BB"]),
+            Valve::new(String::from("BB"), 3, vec!["AA", "CC"]),
+            Valve::new(String::from("CC"), 0, vec!["BB", "DD"]),
+            Valve::new(String::from("DD"), 5, vec!["CC"]),
+            Valve::new(String::from("EE"), 1, vec![]),
+        ];
+        let valves = valves.into_iter().map(|v| (v.name.clone(), v)).collect();
+        let matrix = DistanceMatrix::new(valves);
+        assert_eq!(matrix.distance("AA", "DD"), Some(3));
+        assert_eq!(matrix.distance("DD", "BB"), Some(2));
+        assert_eq!(matrix.distance("AA", "AA"), None);
+        assert_eq!(matrix.distance("AA", "EE"), None);
+
+        let matrix = matrix.with_valves_removed();
+        assert_eq!(matrix.distance("AA", "DD"), Some(3));
+        assert_eq!(matrix.distance("BB", "CC"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use common::{budget, AocResult};
+
 use crate::distance_matrix::DistanceMatrix;
 use crate::player_state::{IntendedMove, PlayerIntention, PlayerState};
 use itertools::Itertools;
@@ -19,29 +21,32 @@ impl GameState {
         GameState {
             players,
             steps_remaining: steps,
-            distance_matrix: distance_matrix,
+            distance_matrix,
             flow: 0,
         }
     }
 
-    pub fn maximize_flow(&self) -> usize {
-        let mut all_flows = self.all_flows();
-        all_flows.sort_by(|a, b| a.flow.cmp(&b.flow));
+    pub fn maximize_flow(&self) -> AocResult<usize> {
+        let mut all_flows = self.all_flows()?;
+        all_flows.sort_by_key(|a| a.flow);
         let max_flow = all_flows.last().unwrap();
-        println!("{}", max_flow);
-        max_flow.flow
+        log::debug!("Best final state: {}", max_flow);
+        Ok(max_flow.flow)
     }
 
-    pub fn all_flows(&self) -> Vec<GameState> {
+    /// Every state the game could end in. This can be a lot of states, so it stops early
+    /// if the runner's budget says so.
+    pub fn all_flows(&self) -> AocResult<Vec<GameState>> {
+        budget::checkpoint()?;
         // Return if we've run out of steps.
-        if self.steps_remaining <= 0 {
-            println!("{}", self.flow);
-            return vec![self.clone()];
+        if self.steps_remaining == 0 {
+            log::trace!("Out of steps with flow {}", self.flow);
+            return Ok(vec![self.clone()]);
         }
         // Return if we've visited every valve.
         if self.enabled_valves().len() == self.distance_matrix.valves.len() {
-            println!("{}", self.flow);
-            return vec![self.clone()];
+            log::trace!("Every valve visited with flow {}", self.flow);
+            return Ok(vec![self.clone()]);
         }
         // If any player has no intended move or valve to enable, then we can't actually take a step yet.
         // Recurse with every possible move for that player.
@@ -51,12 +56,11 @@ impl GameState {
             .enumerate()
             .filter(|(_, p)| p.intention == PlayerIntention::None)
             .collect();
-        if idle_players.len() > 0 {
+        if !idle_players.is_empty() {
             let all_owned_valves: HashSet<String> = self
                 .players
                 .iter()
-                .map(|p| p.owned_valves())
-                .flatten()
+                .flat_map(|p| p.owned_valves())
                 .collect();
             let valves_left_to_visit = self
                 .distance_matrix
@@ -64,18 +68,27 @@ impl GameState {
                 .keys()
                 .filter(|valve_name| !all_owned_valves.contains(*valve_name))
                 .collect::<Vec<&String>>();
-            if valves_left_to_visit.len() > 0 {
+            if !valves_left_to_visit.is_empty() {
                 let mut potential_next_states: Vec<GameState> = vec![];
-                valves_left_to_visit
-                    .iter()
-                    .combinations(idle_players.len())
-                    .for_each(|valves_to_visit| {
-                        // Assign each idle player to a valve.
+                // Each idle player either heads for a valve or stops for good, leaving the
+                // rest to the others: with fewer valves than players some have to stop, and
+                // even when there are enough, another player may get there sooner.
+                let most_assigned = idle_players.len().min(valves_left_to_visit.len());
+                for assigned in 0..=most_assigned {
+                    // Permutations, not combinations: idle players can be in different places,
+                    // so which of them goes where matters.
+                    let assignments = idle_players
+                        .iter()
+                        .combinations(assigned)
+                        .cartesian_product(valves_left_to_visit.iter().permutations(assigned).collect::<Vec<_>>());
+                    for (players, valves_to_visit) in assignments {
                         let mut new_game_state = self.clone();
-                        let mut valve_iter = valves_to_visit.iter();
-                        for (i, p) in idle_players.iter() {
+                        for (i, _) in &idle_players {
+                            new_game_state.players[*i].intention = PlayerIntention::Done;
+                        }
+                        // Assign each chosen player to a valve.
+                        for ((i, p), valve_to_visit) in players.into_iter().zip(valves_to_visit) {
                             let player_position = p.path.last().unwrap();
-                            let valve_to_visit = valve_iter.next().unwrap();
                             let distance = new_game_state
                                 .distance_matrix
                                 .distance(player_position, valve_to_visit)
@@ -87,11 +100,13 @@ impl GameState {
                                 });
                         }
                         potential_next_states.push(new_game_state);
-                    });
-                if potential_next_states.len() > 0 {
+                    }
+                }
+                if !potential_next_states.is_empty() {
                     return potential_next_states
                         .into_iter()
-                        .flat_map(|gs| gs.all_flows())
+                        .map(|gs| gs.all_flows())
+                        .flatten_ok()
                         .collect();
                 }
             }
@@ -107,7 +122,7 @@ impl GameState {
             .players
             .iter()
             .map(|p| match p.intention {
-                PlayerIntention::None => p.clone(),
+                PlayerIntention::None | PlayerIntention::Done => p.clone(),
                 _ => p.take_step(
                     new_game_state.steps_remaining,
                     &new_game_state.distance_matrix,
@@ -120,7 +135,7 @@ impl GameState {
     }
 
     fn enabled_valves(&self) -> HashSet<&String> {
-        self.players.iter().map(|p| &p.path).flatten().collect()
+        self.players.iter().flat_map(|p| &p.path).collect()
     }
 }
 
@@ -133,3 +148,52 @@ impl Display for GameState {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_last_valve_goes_to_one_of_two_idle_players() {
+        // Both players open their first valve at the same time, leaving one valve for
+        // either of them.
+        let text = "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=20; tunnels lead to valves AA, DD
+Valve CC has flow rate=17; tunnel leads to valve AA
+Valve DD has flow rate=14; tunnel leads to valve BB";
+        let distance_matrix = DistanceMatrix::new(parse::parse(text).unwrap()).with_valves_removed();
+        let state = GameState::new(2, Rc::new(distance_matrix), 26);
+        // BB at minute 2, CC at minute 2 and DD at minute 4.
+        assert_eq!(state.maximize_flow(), Ok(24 * 20 + 24 * 17 + 22 * 14));
+    }
+    #[test]
+    fn test_idle_players_can_go_either_way() {
+        // Both players are idle at once, each next to one of the two valves left. Sending
+        // them the other way round takes three minutes longer.
+        let text = "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=20; tunnels lead to valves AA, EE
+Valve CC has flow rate=19; tunnels lead to valves AA, DD
+Valve DD has flow rate=13; tunnel leads to valve CC
+Valve EE has flow rate=11; tunnel leads to valve BB";
+        let distance_matrix = DistanceMatrix::new(parse::parse(text).unwrap()).with_valves_removed();
+        let state = GameState::new(2, Rc::new(distance_matrix), 26);
+        // BB and CC at minute 2, DD and EE at minute 4.
+        assert_eq!(state.maximize_flow(), Ok(24 * 20 + 24 * 19 + 22 * 13 + 22 * 11));
+    }
+    #[test]
+    fn test_idle_player_can_leave_a_valve_to_the_other() {
+        // After opening BB, you're idle with DD still closed, but the elephant can get there
+        // much sooner from CC.
+        let text = "Valve AA has flow rate=0; tunnels lead to valves BB, CA
+Valve BB has flow rate=20; tunnel leads to valve AA
+Valve CA has flow rate=0; tunnels lead to valves AA, CC
+Valve CC has flow rate=16; tunnels lead to valves CA, DA
+Valve DA has flow rate=0; tunnels lead to valves CC, DD
+Valve DD has flow rate=15; tunnel leads to valve DA";
+        let distance_matrix = DistanceMatrix::new(parse::parse(text).unwrap()).with_valves_removed();
+        let state = GameState::new(2, Rc::new(distance_matrix), 26);
+        // BB at minute 2, CC at minute 3 and DD at minute 6.
+        assert_eq!(state.maximize_flow(), Ok(24 * 20 + 23 * 16 + 20 * 15));
+    }
+}
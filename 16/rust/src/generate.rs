@@ -0,0 +1,79 @@
+use common::{Generator, Rng};
+
+use crate::Day16;
+
+/// The search through valves worth opening is exponential, so keep to as many as the real
+/// puzzle has, with as many stuck valves between them.
+const MAX_WORKING_VALVES: usize = 15;
+const VALVES_PER_WORKING_VALVE: usize = 4;
+
+/// A connected network of about `size` valves (at least 3), starting from AA. As in the
+/// real puzzle, one in four (up to 15) have a flow rate, and the rest are stuck in the
+/// corridors between them.
+impl Generator for Day16 {
+    const DEFAULT_SIZE: usize = 60;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.clamp(3, 26 * 26);
+        let working = (size / VALVES_PER_WORKING_VALVE).clamp(1, MAX_WORKING_VALVES);
+        // AA and the working valves are the junctions. A random tree keeps them all
+        // reachable, and a few more corridors make loops.
+        let junctions = working + 1;
+        let mut corridors: Vec<(usize, usize)> = (1..junctions).map(|i| (i, rng.below(i))).collect();
+        for _ in 0..junctions / 3 {
+            let (a, b) = (rng.below(junctions), rng.below(junctions));
+            if a != b && !corridors.contains(&(a, b)) && !corridors.contains(&(b, a)) {
+                corridors.push((a, b));
+            }
+        }
+
+        let mut rates: Vec<i64> = vec![0];
+        rates.extend((0..working).map(|_| rng.range(3..=25)));
+        let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); junctions];
+        let mut stuck = size.saturating_sub(junctions);
+        for (i, (a, b)) in corridors.iter().enumerate() {
+            // Spread the stuck valves over the corridors, one to three in each.
+            let left = corridors.len() - i;
+            let length = (stuck / left).clamp(1, 3);
+            stuck = stuck.saturating_sub(length);
+            let mut previous = *a;
+            for _ in 0..length {
+                tunnels.push(Vec::new());
+                rates.push(0);
+                let valve = tunnels.len() - 1;
+                tunnels[previous].push(valve);
+                tunnels[valve].push(previous);
+                previous = valve;
+            }
+            tunnels[previous].push(*b);
+            tunnels[*b].push(previous);
+        }
+
+        let mut names: Vec<String> = ('A'..='Z')
+            .flat_map(|a| ('A'..='Z').map(move |b| format!("{}{}", a, b)))
+            .skip(1)
+            .collect();
+        rng.shuffle(&mut names);
+        names.truncate(tunnels.len() - 1);
+        names.insert(0, String::from("AA"));
+
+        (0..tunnels.len())
+            .map(|i| {
+                let leads: Vec<&str> = tunnels[i].iter().map(|j| names[*j].as_str()).collect();
+                let (tunnel, valve) = if leads.len() == 1 {
+                    ("tunnel leads", "valve")
+                } else {
+                    ("tunnels lead", "valves")
+                };
+                format!(
+                    "Valve {} has flow rate={}; {} to {} {}\n",
+                    names[i],
+                    rates[i],
+                    tunnel,
+                    valve,
+                    leads.join(", ")
+                )
+            })
+            .collect()
+    }
+}
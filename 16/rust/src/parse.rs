@@ -1,13 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::valve::Valve;
+use common::{parse_lines, AocError, AocResult};
 use regex::Regex;
 
-pub fn parse(contents: String) -> Result<HashMap<String, Valve>, String> {
-    let lines = contents.lines();
-    let valves = lines
-        .map(|line| parse_line(String::from(line)))
-        .collect::<Result<Vec<Valve>, String>>()?;
+pub fn parse(contents: &str) -> AocResult<HashMap<String, Valve>> {
+    let valves = parse_lines(contents, parse_line)?;
+    // Tunnels must lead somewhere we know about.
+    let names: HashSet<&String> = valves.iter().map(|v| &v.name).collect();
+    for (i, (valve, line)) in valves.iter().zip(contents.lines()).enumerate() {
+        if let Some(missing) = valve.linked_valves.iter().find(|v| !names.contains(v)) {
+            let error = AocError::parse(missing, "Tunnel leads to an unknown valve");
+            return Err(error.on_line(i + 1, line));
+        }
+    }
     let valves: HashMap<String, Valve> = valves
         .into_iter()
         .map(|v| (v.name.clone(), v))
@@ -15,33 +21,42 @@ pub fn parse(contents: String) -> Result<HashMap<String, Valve>, String> {
     Ok(valves)
 }
 
-fn parse_line(line: String) -> Result<Valve, String> {
+fn parse_line(line: &str) -> AocResult<Valve> {
     let regex =
-        match Regex::new(r"^Valve (.*) has flow rate=(.*); tunnels? leads? to valves? (.*)$") {
-            Ok(r) => r,
-            Err(e) => return Err(format!("Regex error: {}", e)),
-        };
-    let cap = match regex.captures(&line) {
-        Some(c) => c,
-        None => return Err(format!("Regex failed to match: {}", line)),
-    };
-    let name = cap
-        .get(1)
-        .ok_or_else(|| String::from("No pattern"))?
-        .as_str()
-        .to_string();
-    let rate = cap
-        .get(2)
-        .ok_or_else(|| String::from("No pattern"))?
-        .as_str()
-        .parse::<usize>()
-        .map_err(|e| format!("Failed to parse rate: {}", e))?;
-    let linked_valves: Vec<&str> = cap
-        .get(3)
-        .ok_or_else(|| String::from("No pattern"))?
-        .as_str()
+        Regex::new(r"^Valve (.*) has flow rate=(.*); tunnels? leads? to valves? (.*)$").unwrap();
+    let cap = regex
+        .captures(line)
+        .ok_or(AocError::parse("", "Expected a valve, its flow rate and its tunnels"))?;
+    let name = cap[1].to_string();
+    let rate = cap.get(2).unwrap();
+    let rate = rate.as_str().parse::<usize>().map_err(|e| {
+        let message = format!("Failed to parse rate: {}", e);
+        AocError::parse(rate.as_str(), &message).at(0, rate.start() + 1)
+    })?;
+    let linked_valves: Vec<&str> = cap[3]
         .split(",")
         .map(|s| s.trim())
         .collect();
     Ok(Valve::new(name, rate, linked_valves))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let text = "Valve AA has flow rate=0; tunnels lead to valves BB
+Valve BB has flow rate=x; tunnel leads to valve AA";
+        assert_eq!(
+            parse(text).err(),
+            Some(AocError::parse("x", "Failed to parse rate: invalid digit found in string").at(2, 24))
+        );
+        let text = "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=3; tunnel leads to valve AA";
+        assert_eq!(
+            parse(text).err(),
+            Some(AocError::parse("CC", "Tunnel leads to an unknown valve").at(1, 54))
+        );
+    }
+}
@@ -12,6 +12,8 @@ pub enum PlayerIntention {
     TurningOn { valve: String },
     /// The player has no plan right now.
     None,
+    /// The player has stopped for good, leaving any valves still closed to the others.
+    Done,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -53,18 +55,19 @@ impl PlayerState {
             PlayerIntention::TurningOn { valve } => {
                 let mut new_player_state = self.clone();
                 // If we turn on a valve, we get that valve's flow value for every remaining turn.
-                new_player_state.total_flow += steps_remaining * distance_matrix.flow_at(&valve);
+                new_player_state.total_flow += steps_remaining * distance_matrix.flow_at(valve);
                 new_player_state.path.push(valve.clone());
                 // We've finished turning on the valve, so we're done and have no plan anymore.
                 new_player_state.intention = PlayerIntention::None;
                 new_player_state
             }
-            PlayerIntention::None => {
+            PlayerIntention::None | PlayerIntention::Done => {
                 panic!("PlayerState::take_step called on player with no intention.")
             }
         }
     }
 
+    #[allow(dead_code)]
     pub fn with_new_intention(&self, intention: PlayerIntention) -> PlayerState {
         if self.intention != PlayerIntention::None {
             panic!("PlayerState::with_new_intention called on player with existing intention.");
@@ -98,6 +101,7 @@ impl Display for PlayerState {
         write!(f, "{}", self.path.join("=>"))?;
         match &self.intention {
             PlayerIntention::None => {}
+            PlayerIntention::Done => write!(f, " (done)")?,
             PlayerIntention::Moving(next_move) => {
                 write!(
                     f,
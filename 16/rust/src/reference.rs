@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use common::{AocError, AocResult, Answer, Part};
+
+struct Valve {
+    rate: usize,
+    tunnels: Vec<usize>,
+}
+
+fn parse(contents: &str) -> AocResult<(Vec<Valve>, usize)> {
+    let lines: Vec<Vec<&str>> = contents
+        .lines()
+        .map(|line| line.split([' ', '=', ';', ',']).filter(|s| !s.is_empty()).collect())
+        .collect();
+    let names: Vec<&str> = lines.iter().map(|words| words.get(1).copied().unwrap_or("")).collect();
+    let index = |name: &str| {
+        names
+            .iter()
+            .position(|n| *n == name)
+            .ok_or_else(|| AocError::parse(name, "Unknown valve"))
+    };
+    let mut valves = Vec::new();
+    for words in &lines {
+        // Valve AA has flow rate 0 tunnels lead to valves DD II BB
+        let rate = words.get(5).ok_or(AocError::parse("", "Expected a flow rate"))?.parse()?;
+        let tunnels = words[10..].iter().map(|name| index(name)).collect::<AocResult<_>>()?;
+        valves.push(Valve { rate, tunnels });
+    }
+    Ok((valves, index("AA")?))
+}
+
+/// Everything you (and the elephant) can do in a minute: open the valve you're at, if it
+/// lets anything flow and isn't open yet, go down a tunnel, or stay put.
+fn moves(valves: &[Valve], position: usize, open: u64) -> Vec<(usize, u64)> {
+    let mut moves = vec![(position, open)];
+    if valves[position].rate > 0 && open & (1 << position) == 0 {
+        moves.push((position, open | (1 << position)));
+    }
+    moves.extend(valves[position].tunnels.iter().map(|next| (*next, open)));
+    moves
+}
+
+type State = (usize, usize, usize, u64);
+
+/// The most pressure still to be released with `minutes` left, by trying every move every
+/// minute on the whole network, including the valves with no flow.
+fn most_pressure(valves: &[Valve], state: State, memo: &mut HashMap<State, usize>) -> usize {
+    let (minutes, you, elephant, open) = state;
+    if minutes == 0 {
+        return 0;
+    }
+    if let Some(pressure) = memo.get(&state) {
+        return *pressure;
+    }
+    let flowing: usize = (0..valves.len()).filter(|v| open & (1 << v) != 0).map(|v| valves[v].rate).sum();
+    let mut best = 0;
+    for (you_next, opened) in moves(valves, you, open) {
+        // Without an elephant, it stays at valve usize::MAX.
+        let elephant_moves = if elephant == usize::MAX {
+            vec![(elephant, opened)]
+        } else {
+            moves(valves, elephant, opened)
+        };
+        for (elephant_next, opened) in elephant_moves {
+            let next = (minutes - 1, you_next, elephant_next, opened);
+            best = best.max(most_pressure(valves, next, memo));
+        }
+    }
+    memo.insert(state, flowing + best);
+    flowing + best
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let (valves, start) = parse(contents)?;
+    if valves.len() > 64 {
+        return Err(AocError::solve("Too many valves for the reference solver"));
+    }
+    let state = match part {
+        Part::A => (30, start, usize::MAX, 0),
+        Part::B => (26, start, start, 0),
+    };
+    Ok(Answer::from(most_pressure(&valves, state, &mut HashMap::new())))
+}
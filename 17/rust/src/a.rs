@@ -1,11 +1,14 @@
-use crate::chamber;
+use common::{budget, AocResult, Answer};
 
-pub fn main(contents: String) -> Result<String, String> {
-    let mut chamber = chamber::Chamber::new(contents.trim());
+use crate::chamber::{self, Jet};
+
+pub fn main(jet_pattern: &[Jet]) -> AocResult<Answer> {
+    let mut chamber = chamber::Chamber::new(jet_pattern);
     // Do this 2022 times.
     for i in 0..2022 {
-        println!("Dropping rock {}", i);
+        budget::checkpoint()?;
+        log::debug!("Dropping rock {}", i);
         chamber.drop_rock();
     }
-    Ok((chamber.highest_point() + 1).to_string())
+    Ok(Answer::from(chamber.highest_point() + 1))
 }
\ No newline at end of file
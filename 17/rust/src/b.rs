@@ -1,3 +1,7 @@
-pub fn main(_contents: String) -> Result<String, String> {
-    Ok(String::from("TODO"))
-}
\ No newline at end of file
+use common::{AocError, AocResult, Answer};
+
+use crate::chamber::Jet;
+
+pub fn main(_jet_pattern: &[Jet]) -> AocResult<Answer> {
+    Err(AocError::Unimplemented)
+}
@@ -1,64 +1,88 @@
 use std::collections::HashSet;
+use common::{AocError, AocResult, Point};
 use crate::rock::{Rock, RockDropper};
-use crate::point::Point;
 
-const LOG: bool = false;
 const CHAMBER_WIDTH: i32 = 7;
+// y grows upwards in the chamber, so falling means decreasing y.
+const BELOW: Point = Point::new(0, -1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// Parse a jet pattern like `>><<>`, which must have at least one jet.
+pub fn parse_jets(text: &str) -> AocResult<Vec<Jet>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AocError::parse("", "Empty jet pattern").at(1, 1));
+    }
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(AocError::parse(&c.to_string(), "Unexpected jet direction").at(1, i + 1)),
+        })
+        .collect()
+}
 
 #[derive(Clone)]
 pub struct Chamber {
-    set_rocks: Vec<Rock>,
+    // Every point occupied by a rock that has come to rest, and the highest of them.
+    set_points: HashSet<Point>,
+    highest_point: i32,
     rock_dropper: RockDropper,
-    jet_pattern: Vec<char>,
+    jet_pattern: Vec<Jet>,
     jet_count: usize,
 }
 
 impl Chamber {
-    pub fn new(jet_pattern: &str) -> Chamber {
-        let set_rocks = vec![];
-        let jet_pattern = jet_pattern.chars().collect();
+    pub fn new(jet_pattern: &[Jet]) -> Chamber {
+        let jet_pattern = jet_pattern.to_vec();
         let rock_dropper = RockDropper::new_with_default();
-        Chamber { set_rocks, rock_dropper, jet_pattern, jet_count: 0 }
+        Chamber { set_points: HashSet::new(), highest_point: -1, rock_dropper, jet_pattern, jet_count: 0 }
     }
 
-    fn all_points(&self) -> HashSet<Point> {
-        self.set_rocks
-            .iter()
-            .flat_map(|r| r.points.iter().map(|p| p.clone()))
-            .collect()
+    fn all_points(&self) -> &HashSet<Point> {
+        &self.set_points
+    }
+
+    /// Bring a rock to rest where it is.
+    fn set_rock(&mut self, rock: &Rock) {
+        for point in &rock.points {
+            self.highest_point = self.highest_point.max(point.y);
+            self.set_points.insert(*point);
+        }
     }
 
     pub fn drop_rock(&mut self) {
         let mut rock = self.rock_dropper.drop();
         self.align_dropped_rock(&mut rock);
-        if LOG {
-            let mut fake_chamber = self.clone();
-            fake_chamber.set_rocks.push(rock.clone());
-            println!("{}", fake_chamber);
-        }
+        self.trace_with(&rock);
         loop {
             // Let the rock be pushed by a jet. If it's obstructed, we don't really care.
             let _ = self.push_jet(&mut rock);
-            if LOG {
-                let mut fake_chamber = self.clone();
-                fake_chamber.set_rocks.push(rock.clone());
-                println!("{}", fake_chamber);
-            }
+            self.trace_with(&rock);
             // Let the rock fall one square.
             let stuck = self.push_down(&mut rock).is_err();
-            if LOG {
-                let mut fake_chamber = self.clone();
-                fake_chamber.set_rocks.push(rock.clone());
-                println!("{}", fake_chamber);
-            }
+            self.trace_with(&rock);
             // If it can't fall any more, we're done and can move on.
             if stuck {
                 break;
             }
         }
-        self.set_rocks.push(rock);
-        if LOG {
-            println!("{}", self);
+        self.set_rock(&rock);
+        log::trace!("Rock came to rest:\n{}", self);
+    }
+
+    /// Trace the chamber with a falling rock drawn in, if tracing is on.
+    fn trace_with(&self, rock: &Rock) {
+        if log::log_enabled!(log::Level::Trace) {
+            let mut fake_chamber = self.clone();
+            fake_chamber.set_rock(rock);
+            log::trace!("\n{}", fake_chamber);
         }
     }
 
@@ -68,32 +92,27 @@ impl Chamber {
         let jet = self.jet_pattern[index];
         self.jet_count += 1;
         match jet {
-            '<' => {
-                if self.is_obstructed_left(&rock) {
-                    if LOG {
-                        println!("left obstruction!");
-                    }
+            Jet::Left => {
+                if self.is_obstructed_left(rock) {
+                    log::trace!("left obstruction!");
                     return Err(format!("Rock {:?} is obstructed to the left", rock))
                 }
                 rock.add_x(-1);
             }
-            '>' => {
-                if self.is_obstructed_right(&rock) {
-                    if LOG {
-                        println!("right obstruction!");
-                    }
+            Jet::Right => {
+                if self.is_obstructed_right(rock) {
+                    log::trace!("right obstruction!");
                     return Err(format!("Rock {:?} is obstructed to the right", rock))
                 }
                 rock.add_x(1);
             }
-            _ => panic!("Unexpected jet direction: {}", jet),
         }
         Ok(())
     }
 
     /// Push the rock down one square if it isn't obstructed.
     fn push_down(&self, rock: &mut Rock) -> Result<(), String> {
-        if self.is_obstructed_below(&rock) {
+        if self.is_obstructed_below(rock) {
             return Err(format!("Rock {:?} is obstructed below", rock))
         }
         rock.add_y(-1);
@@ -101,30 +120,18 @@ impl Chamber {
     }
 
     fn is_obstructed_below(&self, rock: &Rock) -> bool {
-        let mut all_points = self.all_points();
-        // Add the floor to the set of points.
-        for x in 0..CHAMBER_WIDTH {
-            all_points.insert(Point { x, y: -1 });
-        }
-        rock.points.iter().any(|p| all_points.contains(&Point { x: p.x, y: p.y-1 }))
+        // The floor is at -1.
+        rock.points.iter().any(|p| p.y == 0 || self.all_points().contains(&(*p + BELOW)))
     }
 
     fn is_obstructed_left(&self, rock: &Rock) -> bool {
-        let mut all_points = self.all_points();
-        // Add the right wall to the set of points.
-        for y in 0..=(self.highest_point() + 5) {
-            all_points.insert(Point { x: -1, y });
-        }
-        rock.points.iter().any(|p| all_points.contains(&Point { x: p.x-1, y: p.y }))
+        // The left wall is at -1.
+        rock.points.iter().any(|p| p.x == 0 || self.all_points().contains(&(*p + Point::LEFT)))
     }
 
     fn is_obstructed_right(&self, rock: &Rock) -> bool {
-        let mut all_points = self.all_points();
-        // Add the right wall to the set of points.
-        for y in 0..=(self.highest_point() + 5) {
-            all_points.insert(Point { x: CHAMBER_WIDTH, y });
-        }
-        rock.points.iter().any(|p| all_points.contains(&Point { x: p.x+1, y: p.y }))
+        // The right wall is at CHAMBER_WIDTH.
+        rock.points.iter().any(|p| p.x == CHAMBER_WIDTH - 1 || self.all_points().contains(&(*p + Point::RIGHT)))
     }
 
     /// Takes a newly-dropped rock and puts it in the right place in the chamber: 2 units from the left edge,
@@ -138,9 +145,8 @@ impl Chamber {
 
     /// Get y-coordinate of the highest object (or the floor).
     pub fn highest_point(&self) -> i32 {
-        let all_points = self.all_points();
         // The floor is at -1, so that's the default if there are no rocks.
-        all_points.iter().map(|p| p.y).max().unwrap_or(-1)
+        self.highest_point
     }
 }
 
@@ -171,9 +177,19 @@ impl std::fmt::Display for Chamber {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_jets() {
+        assert_eq!(parse_jets("<>\n"), Ok(vec![Jet::Left, Jet::Right]));
+        assert_eq!(
+            parse_jets("<>v<"),
+            Err(AocError::parse("v", "Unexpected jet direction").at(1, 3))
+        );
+        assert!(parse_jets("").is_err());
+    }
+
     #[test]
     fn test_is_obstructed_right() {
-        let chamber = Chamber::new(">");
+        let chamber = Chamber::new(&parse_jets(">").unwrap());
         let mut rock = Rock::build_from_text("#");
         // Align the rock, putting it at (2, 3).
         chamber.align_dropped_rock(&mut rock);
@@ -195,22 +211,36 @@ mod tests {
 
     #[test]
     fn test_drop_rock() {
-        let mut chamber = Chamber::new(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+        let mut chamber = Chamber::new(&parse_jets(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>").unwrap());
         let mut expected_points: HashSet<Point> = HashSet::new();
 
         chamber.drop_rock();
-        let new_points = vec![(2, 0), (3, 0), (4, 0), (5, 0)];
+        let new_points = [(2, 0), (3, 0), (4, 0), (5, 0)];
         expected_points.extend(new_points.iter().map(|(x, y)| Point { x: *x, y: *y }));
-        assert_eq!(chamber.all_points(), expected_points);
+        assert_eq!(chamber.all_points(), &expected_points);
 
         chamber.drop_rock();
-        let new_points = vec![(3, 1), (2, 2), (3, 2), (4, 2), (3, 3)];
+        let new_points = [(3, 1), (2, 2), (3, 2), (4, 2), (3, 3)];
         expected_points.extend(new_points.iter().map(|(x, y)| Point { x: *x, y: *y }));
-        assert_eq!(chamber.all_points(), expected_points);
+        assert_eq!(chamber.all_points(), &expected_points);
 
         chamber.drop_rock();
-        let new_points = vec![(0, 3), (1, 3), (2, 3), (2, 4), (2, 5)];
+        let new_points = [(0, 3), (1, 3), (2, 3), (2, 4), (2, 5)];
         expected_points.extend(new_points.iter().map(|(x, y)| Point { x: *x, y: *y }));
-        assert_eq!(chamber.all_points(), expected_points);
+        assert_eq!(chamber.all_points(), &expected_points);
+    }
+
+    #[test]
+    fn test_settled_points_stay_in_step() {
+        // The chamber keeps its settled points and height as rocks land, rather than
+        // working them out again; check them against the rocks themselves.
+        let mut chamber = Chamber::new(&parse_jets(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>").unwrap());
+        for _ in 0..100 {
+            chamber.drop_rock();
+        }
+        // Every five rocks are 4 + 5 + 5 + 4 + 4 points, and no two overlap.
+        assert_eq!(chamber.all_points().len(), 20 * 22);
+        assert_eq!(chamber.highest_point(), chamber.all_points().iter().map(|p| p.y).max().unwrap());
+        assert!(chamber.all_points().iter().all(|p| (0..CHAMBER_WIDTH).contains(&p.x) && p.y >= 0));
     }
 }
\ No newline at end of file
@@ -0,0 +1,13 @@
+use common::{Generator, Rng};
+
+use crate::Day17;
+
+/// A pattern of `size` jets of hot gas.
+impl Generator for Day17 {
+    const DEFAULT_SIZE: usize = 10091;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let jets: String = (0..size.max(1)).map(|_| *rng.choose(&['<', '>'])).collect();
+        jets + "\n"
+    }
+}
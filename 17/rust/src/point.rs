@@ -1,5 +0,0 @@
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
-}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use common::{AocError, AocResult, Answer, Part};
+
+const WIDTH: i64 = 7;
+const ROCK_COUNT: usize = 2022;
+
+/// The five rocks, as (x, y) offsets from their bottom-left corner, with y going up.
+const ROCKS: [&[(i64, i64)]; 5] = [
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+];
+
+/// Whether the rock can be at (x, y) without going through a wall, the floor or another rock.
+fn fits(settled: &HashSet<(i64, i64)>, rock: &[(i64, i64)], x: i64, y: i64) -> bool {
+    rock.iter().all(|(dx, dy)| {
+        let (x, y) = (x + dx, y + dy);
+        (0..WIDTH).contains(&x) && y >= 0 && !settled.contains(&(x, y))
+    })
+}
+
+/// How tall the tower is after `rock_count` rocks, dropping them one square at a time.
+fn tower_height(jets: &[i64], rock_count: usize) -> i64 {
+    let mut settled = HashSet::new();
+    let mut height = 0;
+    let mut jet = 0;
+    for rock in ROCKS.iter().cycle().take(rock_count) {
+        let (mut x, mut y) = (2, height + 3);
+        loop {
+            let push = jets[jet % jets.len()];
+            jet += 1;
+            if fits(&settled, rock, x + push, y) {
+                x += push;
+            }
+            if !fits(&settled, rock, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for (dx, dy) in rock.iter() {
+            settled.insert((x + dx, y + dy));
+            height = height.max(y + dy + 1);
+        }
+    }
+    height
+}
+
+pub fn solve(contents: &str, part: Part) -> AocResult<Answer> {
+    let jets: Vec<i64> = contents
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '<' => Ok(-1),
+            '>' => Ok(1),
+            _ => Err(AocError::parse(&c.to_string(), "Unexpected jet direction")),
+        })
+        .collect::<AocResult<_>>()?;
+    if jets.is_empty() {
+        return Err(AocError::parse("", "Empty jet pattern"));
+    }
+    match part {
+        Part::A => Ok(Answer::from(tower_height(&jets, ROCK_COUNT))),
+        // A trillion rocks is too many to drop one by one.
+        Part::B => Err(AocError::Unimplemented),
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt::Display;
-use crate::point::Point;
+
+use common::Point;
 
 const DEFAULT_ROCK_TEXT: &str = "####
 
@@ -41,19 +42,21 @@ impl Rock {
 
     pub fn build_multiple_from_text(text: &str) -> Vec<Rock> {
         let text_blocks = text.trim().split("\n\n");
-        text_blocks.map(|block| Rock::build_from_text(block)).collect()
+        text_blocks.map(Rock::build_from_text).collect()
     }
 
-    pub fn add_x(&mut self, x: i32) {
+    pub fn shift(&mut self, offset: Point) {
         for point in self.points.iter_mut() {
-            point.x += x;
+            *point += offset;
         }
     }
 
+    pub fn add_x(&mut self, x: i32) {
+        self.shift(Point::new(x, 0));
+    }
+
     pub fn add_y(&mut self, y: i32) {
-        for point in self.points.iter_mut() {
-            point.y += y;
-        }
+        self.shift(Point::new(0, y));
     }
 }
 
@@ -71,7 +74,7 @@ impl Display for Rock {
                     write!(f, ".")?;
                 }
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
         Ok(())
     }
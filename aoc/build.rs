@@ -0,0 +1,71 @@
+//! Generates a `#[test]` for each part of each day's `test_input.txt`, checked against the
+//! answers recorded for it in `answers.toml`. Parts without a recorded answer are generated
+//! as ignored tests, so that they still show up in `cargo test`'s output.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const EXAMPLE_NAME: &str = "test_input.txt";
+const ANSWERS_FILE: &str = "answers.toml";
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap();
+    let answers_path = root.join(ANSWERS_FILE);
+    println!("cargo:rerun-if-changed={}", answers_path.display());
+
+    let recorded = match fs::read_to_string(&answers_path) {
+        Ok(contents) => contents.parse::<toml::Table>().unwrap_or_else(|e| {
+            panic!("{}: {}", answers_path.display(), e);
+        }),
+        Err(_) => toml::Table::new(),
+    };
+
+    let mut tests = String::new();
+    for (day, example) in examples(root) {
+        println!("cargo:rerun-if-changed={}", example.display());
+        let parts = recorded
+            .get(&format!("{:02}", day))
+            .and_then(|inputs| inputs.get(EXAMPLE_NAME))
+            .and_then(|parts| parts.as_table());
+        for part in ["a", "b"] {
+            if !parts.is_some_and(|parts| parts.contains_key(part)) {
+                writeln!(tests, "#[ignore = \"no answer recorded in {}\"]", ANSWERS_FILE).unwrap();
+            }
+            writeln!(
+                tests,
+                "#[test]\nfn example_day{:02}_{}() {{\n    check({}, Part::{}, include_str!({:?}));\n}}\n",
+                day,
+                part,
+                day,
+                part.to_uppercase(),
+                example.display().to_string(),
+            )
+            .unwrap();
+        }
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("examples.rs"), tests).unwrap();
+}
+
+/// Every `NN/test_input.txt` under the repository root, by day.
+fn examples(root: &Path) -> Vec<(u32, PathBuf)> {
+    let mut examples: Vec<(u32, PathBuf)> = fs::read_dir(root)
+        .unwrap()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            if name.len() != 2 {
+                return None;
+            }
+            let day = name.parse::<u32>().ok()?;
+            let example = entry.path().join(EXAMPLE_NAME);
+            example.is_file().then_some((day, example))
+        })
+        .collect();
+    examples.sort();
+    examples
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use common::{AocError, AocResult, Answer, Part};
+use toml::{Table, Value};
+
+/// Known-correct answers, keyed by day, input file name and part. On disk they look like
+///
+/// ```toml
+/// [14."input.txt"]
+/// a = 1234
+/// b = 5678
+/// ```
+///
+/// with multi-line answers stored as an array of rows.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u32, String, Part), Answer>,
+}
+
+/// How an answer compares to the recorded one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Mismatch { expected: Answer },
+    Unrecorded,
+}
+
+impl Answers {
+    /// Load answers from `path`. A missing file just means nothing has been recorded yet.
+    pub fn load(path: &Path) -> AocResult<Answers> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Answers::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> AocResult<Answers> {
+        let table: Table = contents
+            .parse()
+            .map_err(|e: toml::de::Error| AocError::parse("", e.message()))?;
+        let mut answers = Answers::default();
+        for (day_key, inputs) in &table {
+            let day = day_key
+                .parse::<u32>()
+                .map_err(|_| AocError::parse(day_key, "Expected a day number"))?;
+            let inputs = inputs
+                .as_table()
+                .ok_or(AocError::parse(day_key, "Expected a table of input files"))?;
+            for (input_name, parts) in inputs {
+                let parts = parts
+                    .as_table()
+                    .ok_or(AocError::parse(input_name, "Expected a table of parts"))?;
+                for (part_key, value) in parts {
+                    let part = part_key
+                        .parse::<Part>()
+                        .map_err(|_| AocError::parse(part_key, "Expected a or b"))?;
+                    let answer = answer_from_value(value)
+                        .ok_or(AocError::parse(&value.to_string(), "Unsupported answer"))?;
+                    answers.record(day, input_name, part, answer);
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        std::fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    /// Render as TOML, with days zero-padded so that they sort like the day folders.
+    pub fn to_toml(&self) -> String {
+        let mut table = Table::new();
+        for ((day, input_name, part), answer) in &self.answers {
+            let inputs = table
+                .entry(format!("{:02}", day))
+                .or_insert_with(|| Value::Table(Table::new()));
+            let parts = inputs
+                .as_table_mut()
+                .unwrap()
+                .entry(input_name.clone())
+                .or_insert_with(|| Value::Table(Table::new()));
+            parts
+                .as_table_mut()
+                .unwrap()
+                .insert(part.to_string(), answer_to_value(answer));
+        }
+        table.to_string()
+    }
+
+    pub fn get(&self, day: u32, input_name: &str, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, input_name.to_string(), part))
+    }
+
+    pub fn record(&mut self, day: u32, input_name: &str, part: Part, answer: Answer) {
+        self.answers.insert((day, input_name.to_string(), part), answer);
+    }
+
+    pub fn check(&self, day: u32, input_name: &str, part: Part, actual: &Answer) -> Check {
+        match self.get(day, input_name, part) {
+            None => Check::Unrecorded,
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Mismatch {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+fn answer_from_value(value: &Value) -> Option<Answer> {
+    match value {
+        Value::Integer(n) => Some(Answer::Int(*n)),
+        Value::String(s) => Some(Answer::Text(s.clone())),
+        Value::Array(rows) => rows
+            .iter()
+            .map(|row| row.as_str().map(|s| s.to_string()))
+            .collect::<Option<Vec<String>>>()
+            .map(Answer::Art),
+        _ => None,
+    }
+}
+
+fn answer_to_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Int(n) => Value::Integer(*n),
+        Answer::Text(s) => Value::String(s.clone()),
+        Answer::Art(rows) => Value::Array(rows.iter().cloned().map(Value::String).collect()),
+    }
+}
+
+/// A line-by-line diff of two answers: unchanged lines are indented, and changed ones are
+/// shown as `- expected` / `+ actual`.
+pub fn diff(expected: &Answer, actual: &Answer) -> String {
+    let expected = expected.to_string();
+    let actual = actual.to_string();
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut lines = vec![];
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => lines.push(format!("  {}", e)),
+            (e, a) => {
+                if let Some(e) = e {
+                    lines.push(format!("- {}", e));
+                }
+                if let Some(a) = a {
+                    lines.push(format!("+ {}", a));
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(10, "input.txt", Part::B, Answer::Art(vec![String::from("#.")]));
+        answers.record(5, "input.txt", Part::A, Answer::from("CMZ"));
+        answers.record(14, "test_input.txt", Part::A, Answer::from(24));
+        let toml = answers.to_toml();
+        assert!(toml.find("[05").unwrap() < toml.find("[10").unwrap());
+
+        let loaded = Answers::parse(&toml).unwrap();
+        assert_eq!(loaded.answers, answers.answers);
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[06.\"input.txt\"]\na = 7\n").unwrap();
+        assert_eq!(answers.check(6, "input.txt", Part::A, &Answer::from(7)), Check::Pass);
+        assert_eq!(
+            answers.check(6, "input.txt", Part::A, &Answer::from(8)),
+            Check::Mismatch {
+                expected: Answer::from(7)
+            }
+        );
+        assert_eq!(answers.check(6, "input.txt", Part::B, &Answer::from(7)), Check::Unrecorded);
+        assert!(Answers::parse("[x.\"input.txt\"]\na = 7\n").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let expected = Answer::Art(vec![String::from("##"), String::from("..")]);
+        let actual = Answer::Art(vec![String::from("##"), String::from(".#")]);
+        assert_eq!(diff(&expected, &actual), "  ##\n- ..\n+ .#");
+        assert_eq!(diff(&Answer::from(1), &Answer::from(2)), "- 1\n+ 2");
+    }
+}
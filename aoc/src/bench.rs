@@ -0,0 +1,206 @@
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use common::{AocError, AocResult, Part, Solution};
+use serde::{Deserialize, Serialize};
+
+/// The spread of timings over a number of iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings for one day: parsing, then each part against the parsed input. A part that
+/// fails keeps its error instead.
+pub struct Timings {
+    pub parse: Stats,
+    pub parts: Vec<(Part, AocResult<Stats>)>,
+}
+
+/// Parse `contents` and solve both parts, `iterations` times over.
+pub fn measure<S: Solution>(contents: &str, iterations: usize) -> AocResult<Timings> {
+    let iterations = iterations.max(1);
+    let parts = [Part::A, Part::B];
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut part_samples: Vec<AocResult<Vec<Duration>>> = vec![Ok(vec![]), Ok(vec![])];
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let input = S::parse(black_box(contents))?;
+        parse_samples.push(start.elapsed());
+
+        for (i, part) in parts.iter().enumerate() {
+            let Ok(samples) = &mut part_samples[i] else {
+                continue;
+            };
+            let start = Instant::now();
+            let result = black_box(S::solve_part(&input, *part));
+            let elapsed = start.elapsed();
+            match result {
+                Ok(_) => samples.push(elapsed),
+                Err(e) => part_samples[i] = Err(e),
+            }
+        }
+    }
+    Ok(Timings {
+        parse: Stats::from_samples(parse_samples),
+        parts: parts
+            .into_iter()
+            .zip(part_samples)
+            .map(|(part, samples)| (part, samples.map(Stats::from_samples)))
+            .collect(),
+    })
+}
+
+/// One timed stage ("parse", "a" or "b") of one day, as stored in a baseline file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u32,
+    pub stage: String,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Entry {
+    pub fn new(day: u32, stage: &str, stats: &Stats) -> Entry {
+        Entry {
+            day,
+            stage: stage.to_string(),
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            max_ns: stats.max.as_nanos() as u64,
+        }
+    }
+}
+
+/// A saved benchmark run to compare later runs against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub input: String,
+    pub iterations: usize,
+    pub entries: Vec<Entry>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> AocResult<Baseline> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AocError::Io(format!("{}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents).map_err(|e| {
+            AocError::parse("", &format!("Invalid baseline: {}", e)).at(e.line(), e.column())
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| AocError::Io(e.to_string()))?;
+        std::fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    pub fn find(&self, day: u32, stage: &str) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.day == day && e.stage == stage)
+    }
+}
+
+/// The relative change in median time against a baseline, e.g. `0.25` for 25% slower.
+pub fn change(baseline: &Entry, current: &Entry) -> f64 {
+    if baseline.median_ns == 0 {
+        return 0.0;
+    }
+    (current.median_ns as f64 - baseline.median_ns as f64) / baseline.median_ns as f64
+}
+
+/// Format a duration with a unit that keeps it short, e.g. `512ns`, `3.41ms` or `2.05s`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Answer;
+
+    struct Slow;
+
+    impl Solution for Slow {
+        type Input = u64;
+
+        fn parse(contents: &str) -> AocResult<u64> {
+            Ok(contents.trim().parse()?)
+        }
+
+        fn part_a(input: &u64) -> AocResult<Answer> {
+            Ok(Answer::from((0..*input).sum::<u64>() as i64))
+        }
+
+        fn part_b(_input: &u64) -> AocResult<Answer> {
+            Err(AocError::Unimplemented)
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 3, 2, 4].map(Duration::from_millis).to_vec();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_measure() {
+        let timings = measure::<Slow>("1000", 3).unwrap();
+        assert!(timings.parse.min <= timings.parse.max);
+        assert!(timings.parts[0].1.is_ok());
+        assert_eq!(timings.parts[1].1, Err(AocError::Unimplemented));
+        assert!(measure::<Slow>("x", 3).is_err());
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stats = Stats::from_samples(vec![Duration::from_micros(10)]);
+        let baseline = Baseline {
+            input: String::from("input.txt"),
+            iterations: 1,
+            entries: vec![Entry::new(6, "parse", &stats)],
+        };
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+        assert_eq!(baseline.find(6, "parse").map(|e| e.median_ns), Some(10_000));
+        assert!(baseline.find(6, "a").is_none());
+    }
+
+    #[test]
+    fn test_change_and_format() {
+        let stats = Stats::from_samples(vec![Duration::from_micros(100)]);
+        let slower = Stats::from_samples(vec![Duration::from_micros(125)]);
+        let change = change(&Entry::new(1, "a", &stats), &Entry::new(1, "a", &slower));
+        assert!((change - 0.25).abs() < 1e-9);
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+        assert_eq!(format_duration(Duration::from_micros(3410)), "3.41ms");
+        assert_eq!(format_duration(Duration::from_millis(2050)), "2.05s");
+    }
+}
@@ -0,0 +1,84 @@
+use common::{AocResult, Answer, Part, Solution};
+
+use crate::bench::{self, Timings};
+use crate::report::{self, Timed};
+
+/// Parses an input once and runs each of the requested parts against it.
+pub type Solver = fn(&str, &[Part]) -> AocResult<Vec<AocResult<Answer>>>;
+
+/// Times parsing and each part over a number of iterations.
+pub type Bencher = fn(&str, usize) -> AocResult<Timings>;
+
+/// Like `Solver`, but also times parsing and each part.
+pub type TimedSolver = fn(&str, &[Part]) -> AocResult<Timed>;
+
+/// Solves one part the slow, obviously correct way.
+pub type ReferenceSolver = fn(&str, Part) -> AocResult<Answer>;
+
+/// An entry in the runner's dispatch table.
+pub struct Day {
+    pub number: u32,
+    pub solve: Solver,
+    pub bench: Bencher,
+    pub timed: TimedSolver,
+    pub reference: ReferenceSolver,
+}
+
+impl Day {
+    fn new<S: Solution>(number: u32) -> Day {
+        Day {
+            number,
+            solve: S::solve,
+            bench: bench::measure::<S>,
+            timed: report::timed::<S>,
+            reference: S::reference,
+        }
+    }
+}
+
+/// Every day that the runner knows how to dispatch to, in order.
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Day01>(1),
+        Day::new::<day02::Day02>(2),
+        Day::new::<day03::Day03>(3),
+        Day::new::<day04::Day04>(4),
+        Day::new::<day05::Day05>(5),
+        Day::new::<day06::Day06>(6),
+        Day::new::<day07::Day07>(7),
+        Day::new::<day08::Day08>(8),
+        Day::new::<day09::Day09>(9),
+        Day::new::<day10::Day10>(10),
+        Day::new::<day11::Day11>(11),
+        Day::new::<day12::Day12>(12),
+        Day::new::<day13::Day13>(13),
+        Day::new::<day14::Day14>(14),
+        Day::new::<day15::Day15>(15),
+        Day::new::<day16::Day16>(16),
+        Day::new::<day17::Day17>(17),
+    ]
+}
+
+pub fn find(number: u32) -> Option<Day> {
+    all().into_iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let numbers: Vec<u32> = all().iter().map(|d| d.number).collect();
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(numbers, sorted);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(6).map(|d| d.number), Some(6));
+        assert!(find(25).is_none());
+    }
+}
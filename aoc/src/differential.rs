@@ -0,0 +1,112 @@
+//! Differential testing: run each day's fast solution and its slow reference solver on the
+//! same generated inputs, and flag any part where they come up with different answers.
+
+use common::{AocError, AocResult, Answer, Part, Rng};
+
+use crate::days::Day;
+use crate::generate::DayGenerator;
+
+const PARTS: [Part; 2] = [Part::A, Part::B];
+/// Small enough that the reference solvers finish in a second or so.
+pub const DEFAULT_SIZE: usize = 12;
+pub const DEFAULT_SEEDS: u64 = 5;
+
+/// How a part fared across all the seeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Both solvers gave the same answers on this many inputs.
+    Agrees(usize),
+    /// The first seed whose input they disagreed on, with what each made of it.
+    Disagrees {
+        seed: u64,
+        fast: AocResult<Answer>,
+        reference: AocResult<Answer>,
+    },
+    /// The day has no reference solver for this part.
+    NoReference,
+}
+
+impl Outcome {
+    /// One line for a results table, e.g. `agrees on 5 inputs`.
+    pub fn summary(&self, day: u32, size: usize) -> String {
+        match self {
+            Outcome::Agrees(count) => format!("agrees on {} input(s)", count),
+            Outcome::NoReference => String::from("no reference solver"),
+            Outcome::Disagrees { seed, fast, reference } => format!(
+                "DISAGREE: fast {}, reference {} (aoc generate {} --seed {} --size {})",
+                show(fast),
+                show(reference),
+                day,
+                seed,
+                size
+            ),
+        }
+    }
+}
+
+fn show(result: &AocResult<Answer>) -> String {
+    match result {
+        Ok(answer) if answer.is_multiline() => String::from("(picture)"),
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+/// Generate an input for each seed in `0..seeds`, `size` items big, and compare the day's
+/// solutions on it part by part.
+pub fn compare(day: &Day, generator: &DayGenerator, seeds: u64, size: usize) -> Vec<(Part, Outcome)> {
+    let mut outcomes: Vec<(Part, Outcome)> = PARTS.map(|part| (part, Outcome::Agrees(0))).to_vec();
+    for seed in 0..seeds {
+        let input = (generator.generate)(&mut Rng::new(seed), size);
+        let fast = (day.solve)(&input, &PARTS).unwrap_or_else(|e| vec![Err(e); PARTS.len()]);
+        for ((part, outcome), fast) in outcomes.iter_mut().zip(fast) {
+            let Outcome::Agrees(count) = outcome else {
+                continue;
+            };
+            let reference = (day.reference)(&input, *part);
+            if reference == Err(AocError::Unimplemented) {
+                *outcome = Outcome::NoReference;
+            } else if fast == reference {
+                *count += 1;
+            } else {
+                *outcome = Outcome::Disagrees { seed, fast, reference };
+            }
+        }
+    }
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, generate};
+
+    #[test]
+    #[ignore = "slow in debug builds: run with --release -- --ignored"]
+    fn test_fast_and_reference_solutions_agree() {
+        for day in days::all() {
+            let Some(generator) = generate::find(day.number) else {
+                continue;
+            };
+            for (part, outcome) in compare(&day, &generator, 3, DEFAULT_SIZE) {
+                if let Outcome::Disagrees { .. } = outcome {
+                    panic!("day {} part {}: {}", day.number, part, outcome.summary(day.number, DEFAULT_SIZE));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let outcome = Outcome::Disagrees {
+            seed: 3,
+            fast: Ok(Answer::from(7)),
+            reference: Err(AocError::NoAnswer),
+        };
+        assert_eq!(
+            outcome.summary(14, 12),
+            "DISAGREE: fast 7, reference error: no answer found (aoc generate 14 --seed 3 --size 12)"
+        );
+        assert_eq!(Outcome::Agrees(5).summary(14, 12), "agrees on 5 input(s)");
+    }
+}
@@ -0,0 +1,19 @@
+//! End-to-end tests of every day against its `test_input.txt`, generated by `build.rs`.
+
+use common::{Answer, Part};
+
+use crate::answers::Answers;
+use crate::days;
+
+const EXAMPLE_NAME: &str = "test_input.txt";
+
+fn check(day: u32, part: Part, contents: &str) {
+    let answers = Answers::parse(include_str!("../../answers.toml")).unwrap();
+    let expected = answers.get(day, EXAMPLE_NAME, part).unwrap();
+    let solver = days::find(day).unwrap_or_else(|| panic!("Day {} isn't registered", day));
+    let mut results = (solver.solve)(contents, &[part]).unwrap();
+    let actual: Answer = results.remove(0).unwrap();
+    assert_eq!(&actual, expected, "day {} part {}", day, part);
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
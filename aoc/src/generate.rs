@@ -0,0 +1,101 @@
+use common::{Generator, Rng};
+
+/// Writes a random input of a given size.
+pub type Generate = fn(&mut Rng, usize) -> String;
+
+/// A day's input generator, with how big a real puzzle input is.
+pub struct DayGenerator {
+    pub number: u32,
+    pub generate: Generate,
+    pub default_size: usize,
+}
+
+impl DayGenerator {
+    fn new<G: Generator>(number: u32) -> DayGenerator {
+        DayGenerator {
+            number,
+            generate: G::generate,
+            default_size: G::DEFAULT_SIZE,
+        }
+    }
+}
+
+/// Every day with an input generator, in order.
+pub fn all() -> Vec<DayGenerator> {
+    vec![
+        DayGenerator::new::<day01::Day01>(1),
+        DayGenerator::new::<day02::Day02>(2),
+        DayGenerator::new::<day03::Day03>(3),
+        DayGenerator::new::<day04::Day04>(4),
+        DayGenerator::new::<day05::Day05>(5),
+        DayGenerator::new::<day06::Day06>(6),
+        DayGenerator::new::<day07::Day07>(7),
+        DayGenerator::new::<day08::Day08>(8),
+        DayGenerator::new::<day09::Day09>(9),
+        DayGenerator::new::<day10::Day10>(10),
+        DayGenerator::new::<day11::Day11>(11),
+        DayGenerator::new::<day12::Day12>(12),
+        DayGenerator::new::<day13::Day13>(13),
+        DayGenerator::new::<day14::Day14>(14),
+        DayGenerator::new::<day15::Day15>(15),
+        DayGenerator::new::<day16::Day16>(16),
+        DayGenerator::new::<day17::Day17>(17),
+    ]
+}
+
+pub fn find(number: u32) -> Option<DayGenerator> {
+    all().into_iter().find(|g| g.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use common::{AocError, Part};
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for generator in all() {
+            let input = |seed| (generator.generate)(&mut Rng::new(seed), 20);
+            assert_eq!(input(1), input(1), "day {}", generator.number);
+            assert_ne!(input(1), input(2), "day {}", generator.number);
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs_parse() {
+        for generator in all() {
+            let Some(day) = days::find(generator.number) else {
+                continue;
+            };
+            for seed in 0..5 {
+                let input = (generator.generate)(&mut Rng::new(seed), 12);
+                if let Err(e) = (day.solve)(&input, &[]) {
+                    panic!("day {} seed {}: {}\n{}", day.number, seed, e, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "slow in debug builds: run with --release -- --ignored"]
+    fn test_generated_inputs_solve() {
+        // Day 16's search grows quickly with the number of working valves.
+        const SIZE: usize = 30;
+        for generator in all() {
+            let Some(day) = days::find(generator.number) else {
+                continue;
+            };
+            for seed in 0..3 {
+                let input = (generator.generate)(&mut Rng::new(seed), SIZE);
+                let results = (day.solve)(&input, &[Part::A, Part::B]).unwrap();
+                for (part, result) in [Part::A, Part::B].iter().zip(results) {
+                    match result {
+                        Ok(_) | Err(AocError::Unimplemented) => {}
+                        Err(e) => panic!("day {} part {} seed {}: {}", day.number, part, seed, e),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use common::{AocError, AocResult};
+
+pub const DEFAULT_YEAR: u32 = 2022;
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+// The site asks automated tools to say who they are.
+const USER_AGENT: &str = "aoc-2022-runner (personal puzzle input cache)";
+const SESSION_FILE: &str = "session";
+
+/// Which of a day's inputs: the personal puzzle input, or the example from the puzzle text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Input,
+    Example,
+}
+
+impl Kind {
+    /// The file name for this kind, both in the store and in the day folders.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Kind::Input => "input.txt",
+            Kind::Example => "test_input.txt",
+        }
+    }
+
+    pub fn from_file_name(name: &str) -> Option<Kind> {
+        [Kind::Input, Kind::Example]
+            .into_iter()
+            .find(|kind| kind.file_name() == name)
+    }
+
+    pub fn from_arg(arg: &str) -> Option<Kind> {
+        match arg {
+            "input" => Some(Kind::Input),
+            "example" => Some(Kind::Example),
+            _ => None,
+        }
+    }
+}
+
+/// Downloads puzzle inputs from `<base_url>/<year>/day/<day>/input`, logged in with the
+/// session cookie.
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Fetcher {
+    pub fn fetch(&self, year: u32, day: u32) -> AocResult<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url.trim_end_matches('/'), year, day);
+        log::debug!("Fetching {}", url);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| AocError::Io(format!("{}: {}", url, e))),
+            Err(ureq::Error::Status(code @ (400 | 401), _)) => Err(AocError::Io(format!(
+                "{}: HTTP {} (is the session token still valid?)",
+                url, code
+            ))),
+            Err(ureq::Error::Status(code, _)) => Err(AocError::Io(format!("{}: HTTP {}", url, code))),
+            Err(e) => Err(AocError::Io(format!("{}: {}", url, e))),
+        }
+    }
+}
+
+/// Puzzle inputs, cached at `<cache_dir>/<year>/<day>/input.txt` (or `test_input.txt`).
+/// Anything not in the cache is looked for in the repository's `NN/` day folders, and
+/// missing puzzle inputs can be fetched if there's a session token.
+pub struct InputStore {
+    pub cache_dir: PathBuf,
+    pub year: u32,
+    pub repo_root: PathBuf,
+    pub fetcher: Option<Fetcher>,
+}
+
+impl InputStore {
+    /// Configure the store from the environment:
+    /// - `AOC_CACHE_DIR`, defaulting to `$XDG_CACHE_HOME/aoc` or `~/.cache/aoc`
+    /// - `AOC_YEAR`, defaulting to 2022
+    /// - `AOC_SESSION`, or else the contents of `<cache dir>/session`, to enable fetching
+    /// - `AOC_URL`, where to fetch from, defaulting to the Advent of Code site
+    pub fn from_env(repo_root: PathBuf) -> AocResult<InputStore> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let cache_dir = match var("AOC_CACHE_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => var("XDG_CACHE_HOME")
+                .map(PathBuf::from)
+                .or_else(|| var("HOME").map(|home| Path::new(&home).join(".cache")))
+                .unwrap_or_else(|| repo_root.join(".cache"))
+                .join("aoc"),
+        };
+        let year = match var("AOC_YEAR") {
+            Some(year) => year
+                .parse()
+                .map_err(|_| AocError::Usage(format!("Invalid AOC_YEAR: {}", year)))?,
+            None => DEFAULT_YEAR,
+        };
+        let session = var("AOC_SESSION").or_else(|| {
+            let session = fs::read_to_string(cache_dir.join(SESSION_FILE)).ok()?;
+            Some(session.trim().to_string()).filter(|s| !s.is_empty())
+        });
+        let fetcher = session.map(|session| Fetcher {
+            base_url: var("AOC_URL").unwrap_or_else(|| String::from(DEFAULT_BASE_URL)),
+            session,
+        });
+        Ok(InputStore {
+            cache_dir,
+            year,
+            repo_root,
+            fetcher,
+        })
+    }
+
+    /// Where the store keeps an input, whether or not it's there yet.
+    pub fn path(&self, year: u32, day: u32, kind: Kind) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("{:02}", day))
+            .join(kind.file_name())
+    }
+
+    /// Find the file called `input_name` for a day of this year: the cached copy if it's
+    /// one of the kinds the store knows about, or else the one in the day folder. The day
+    /// folders hold this repo's own year, so other years only look in the store.
+    pub fn find(&self, day: u32, input_name: &str) -> Option<PathBuf> {
+        let cached = Kind::from_file_name(input_name).map(|kind| self.path(self.year, day, kind));
+        let loose = self.loose_path(day, input_name);
+        cached.into_iter().chain(loose).find(|path| path.is_file())
+    }
+
+    /// Where a day folder would hold `input_name`, if this is the repo's own year.
+    fn loose_path(&self, day: u32, input_name: &str) -> Option<PathBuf> {
+        (self.year == DEFAULT_YEAR).then(|| self.repo_root.join(format!("{:02}", day)).join(input_name))
+    }
+
+    /// Like `find`, but fetch a missing puzzle input into the store if we can.
+    pub fn resolve(&self, day: u32, kind: Kind) -> AocResult<PathBuf> {
+        if let Some(path) = self.find(day, kind.file_name()) {
+            return Ok(path);
+        }
+        match (&self.fetcher, kind) {
+            (Some(fetcher), Kind::Input) => {
+                let contents = fetcher.fetch(self.year, day)?;
+                self.save(self.year, day, kind, &contents)
+            }
+            _ => {
+                let mut places = self.path(self.year, day, kind).display().to_string();
+                if let Some(loose) = self.loose_path(day, kind.file_name()) {
+                    places = format!("{} or {}", places, loose.display());
+                }
+                Err(AocError::Io(format!(
+                    "No {} for day {} of {} in {}; import it with `aoc inputs import`{}",
+                    kind.file_name(),
+                    day,
+                    self.year,
+                    places,
+                    if kind == Kind::Input { ", or set AOC_SESSION to fetch it" } else { "" }
+                )))
+            }
+        }
+    }
+
+    pub fn save(&self, year: u32, day: u32, kind: Kind, contents: &str) -> AocResult<PathBuf> {
+        let path = self.path(year, day, kind);
+        let io_error = |e: std::io::Error| AocError::Io(format!("{}: {}", path.display(), e));
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(&path, contents).map_err(io_error)?;
+        Ok(path)
+    }
+
+    /// Copy every input found in a directory or a tarball (`.tar`, `.tar.gz` or `.tgz`)
+    /// into the store, returning where each one went. Inputs are recognised by their paths,
+    /// as in this repository: `14/input.txt`, `2022/14/test_input.txt` and so on.
+    pub fn import(&self, source: &Path) -> AocResult<Vec<PathBuf>> {
+        let io_error = |e: std::io::Error| AocError::Io(format!("{}: {}", source.display(), e));
+        let mut imported = Vec::new();
+        if source.is_dir() {
+            for file in files_under(source).map_err(io_error)? {
+                if let Some((year, day, kind)) = classify(&file, self.year) {
+                    let contents = fs::read_to_string(&file)
+                        .map_err(|e| AocError::Io(format!("{}: {}", file.display(), e)))?;
+                    imported.push(self.save(year, day, kind, &contents)?);
+                }
+            }
+            return Ok(imported);
+        }
+        let file = fs::File::open(source).map_err(io_error)?;
+        let name = source.to_string_lossy();
+        let reader: Box<dyn Read> = if name.ends_with(".gz") || name.ends_with(".tgz") {
+            Box::new(flate2::read::GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries().map_err(io_error)? {
+            let mut entry = entry.map_err(io_error)?;
+            let path = entry.path().map_err(io_error)?.into_owned();
+            if let Some((year, day, kind)) = classify(&path, self.year) {
+                let mut contents = String::new();
+                entry.read_to_string(&mut contents).map_err(io_error)?;
+                imported.push(self.save(year, day, kind, &contents)?);
+            }
+        }
+        Ok(imported)
+    }
+}
+
+/// Work out which input a file holds from its path: the file name gives the kind, the
+/// folder it's in the day, and the folder above that the year if it looks like one.
+fn classify(path: &Path, default_year: u32) -> Option<(u32, u32, Kind)> {
+    let mut components = path.iter().rev().map(|c| c.to_str());
+    let kind = Kind::from_file_name(components.next()??)?;
+    let day = components
+        .next()??
+        .parse::<u32>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))?;
+    let year = components
+        .next()
+        .flatten()
+        .and_then(|c| c.parse::<u32>().ok())
+        .filter(|year| *year >= 2015)
+        .unwrap_or(default_year);
+    Some((year, day, kind))
+}
+
+/// Every file in a directory tree, skipping build output and hidden directories.
+fn files_under(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                files.extend(files_under(&path)?);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    /// An empty scratch directory for one test.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn store(dir: &Path) -> InputStore {
+        InputStore {
+            cache_dir: dir.join("cache"),
+            year: DEFAULT_YEAR,
+            repo_root: dir.join("repo"),
+            fetcher: None,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let classify = |path: &str| classify(Path::new(path), 2022);
+        assert_eq!(classify("14/input.txt"), Some((2022, 14, Kind::Input)));
+        assert_eq!(classify("x/2021/03/test_input.txt"), Some((2021, 3, Kind::Example)));
+        assert_eq!(classify("14/rust/input.txt"), None);
+        assert_eq!(classify("26/input.txt"), None);
+        assert_eq!(classify("14/notes.txt"), None);
+        assert_eq!(classify("input.txt"), None);
+    }
+
+    #[test]
+    fn test_find_prefers_the_store() {
+        let dir = scratch("find");
+        let store = store(&dir);
+        fs::create_dir_all(dir.join("repo/14")).unwrap();
+        fs::write(dir.join("repo/14/input.txt"), "loose").unwrap();
+        fs::write(dir.join("repo/14/other.txt"), "other").unwrap();
+        assert_eq!(store.find(14, "input.txt"), Some(dir.join("repo/14/input.txt")));
+        assert_eq!(store.find(14, "other.txt"), Some(dir.join("repo/14/other.txt")));
+        assert_eq!(store.find(15, "input.txt"), None);
+
+        let cached = store.save(2022, 14, Kind::Input, "cached").unwrap();
+        assert_eq!(store.find(14, "input.txt"), Some(cached));
+        assert!(store.resolve(15, Kind::Example).is_err());
+    }
+
+    #[test]
+    fn test_find_other_years_only_in_the_store() {
+        let dir = scratch("find_year");
+        let store = InputStore { year: 2021, ..store(&dir) };
+        fs::create_dir_all(dir.join("repo/14")).unwrap();
+        fs::write(dir.join("repo/14/input.txt"), "2022").unwrap();
+        assert_eq!(store.find(14, "input.txt"), None);
+        let error = store.resolve(14, Kind::Input).unwrap_err().to_string();
+        assert!(error.contains("day 14 of 2021"), "{}", error);
+        assert!(!error.contains(&dir.join("repo/14").display().to_string()), "{}", error);
+
+        let cached = store.save(2021, 14, Kind::Input, "2021").unwrap();
+        assert_eq!(store.find(14, "input.txt"), Some(cached));
+    }
+
+    #[test]
+    fn test_import() {
+        let dir = scratch("import");
+        let store = store(&dir);
+        fs::create_dir_all(dir.join("repo/03/rust")).unwrap();
+        fs::write(dir.join("repo/03/input.txt"), "three").unwrap();
+        fs::write(dir.join("repo/03/rust/input.txt"), "ignored").unwrap();
+        assert_eq!(store.import(&dir.join("repo")).unwrap().len(), 1);
+        assert_eq!(fs::read_to_string(store.path(2022, 3, Kind::Input)).unwrap(), "three");
+
+        let tarball = dir.join("inputs.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&tarball).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        for (path, contents) in [("2021/07/test_input.txt", "seven"), ("README", "skip me")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, path, contents.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+        let imported = store.import(&tarball).unwrap();
+        assert_eq!(imported, vec![store.path(2021, 7, Kind::Example)]);
+        assert_eq!(fs::read_to_string(&imported[0]).unwrap(), "seven");
+    }
+
+    #[test]
+    fn test_fetch_from_stand_in_server() {
+        let dir = scratch("fetch");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        // Answer exactly one request, handing back what was asked for.
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..n]);
+            }
+            let body = "1\n2\n3\n";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        let store = InputStore {
+            fetcher: Some(Fetcher {
+                base_url: format!("http://{}/", address),
+                session: String::from("secret"),
+            }),
+            ..store(&dir)
+        };
+        let path = store.resolve(9, Kind::Input).unwrap();
+        assert_eq!(path, store.path(2022, 9, Kind::Input));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/9/input HTTP/1.1\r\n"));
+        assert!(request.to_lowercase().contains("cookie: session=secret\r\n"));
+        // The server is gone now, so this must come from the cache.
+        assert_eq!(store.resolve(9, Kind::Input).unwrap(), path);
+        // Examples aren't fetched.
+        assert!(store.resolve(9, Kind::Example).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::{Answer, Part};
+
+/// How long another language's solution gets before it's given up on. Generous, since sbt
+/// can take most of a minute just to start.
+pub const TIMEOUT: Duration = Duration::from_secs(120);
+// How often to check whether a solution has finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The languages the puzzles are solved in, in the order of the README's columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Python,
+    Go,
+    Rust,
+    Scala,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::Python, Language::Go, Language::Rust, Language::Scala];
+
+    pub fn from_arg(arg: &str) -> Option<Language> {
+        Language::ALL
+            .into_iter()
+            .find(|language| language.name().eq_ignore_ascii_case(arg))
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::Python => "Python",
+            Language::Go => "Go",
+            Language::Rust => "Rust",
+            Language::Scala => "Scala",
+        }
+    }
+}
+
+/// The command running one part of a day in another language against `input`, or `None`
+/// if there's no solution in that language.
+pub fn external_command(
+    root: &Path,
+    language: Language,
+    day: u32,
+    part: Part,
+    input: &Path,
+) -> Option<Command> {
+    let day_dir = root.join(format!("{:02}", day));
+    let (program, args, dir) = match language {
+        Language::Python => {
+            let script = day_dir.join("python").join(format!("{:02}{}.py", day, part));
+            if !script.is_file() {
+                return None;
+            }
+            let script = script.to_string_lossy().into_owned();
+            ("python3", vec![script, input.to_string_lossy().into_owned()], root.to_path_buf())
+        }
+        Language::Go => {
+            if !day_dir.join("go").join("go.mod").is_file() {
+                return None;
+            }
+            let args = ["run", ".", &part.to_string(), &input.to_string_lossy()].map(String::from);
+            ("go", args.to_vec(), day_dir.join("go"))
+        }
+        Language::Scala => {
+            // Days 1 to 3 are their own projects; later ones share the one in `scala`.
+            let (project, run) = if day_dir.join("scala").join("build.sbt").is_file() {
+                (day_dir.join("scala"), format!("run {} {}", part, input.display()))
+            } else if root.join("scala/src/main/scala").join(format!("day{:02}", day)).is_dir() {
+                (root.join("scala"), format!("run {} {} {}", day, part, input.display()))
+            } else {
+                return None;
+            };
+            ("sbt", vec![String::from("--batch"), run], project)
+        }
+        Language::Rust => return None,
+    };
+    let mut command = Command::new(program);
+    command.args(args).current_dir(dir);
+    Some(command)
+}
+
+/// Run `command` to completion and capture its output, like `Command::output`, but kill it
+/// and return `None` if it's still running after `limit`.
+pub fn output_with_timeout(command: &mut Command, limit: Duration) -> io::Result<Option<Output>> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Drain the pipes as the program writes, so that it never blocks on a full one.
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let deadline = Instant::now() + limit;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+/// Whether a program's output ends with the answer: on its last non-empty line (perhaps
+/// after a `Result: ` label) or, for a picture, as its last lines. Anything printed earlier
+/// is debugging, and doesn't count. Pictures are compared on their lit pixels, since some
+/// solutions draw the dark ones as `.`.
+pub fn output_matches(stdout: &[u8], expected: &Answer) -> bool {
+    let normalise = |line: &str| -> String {
+        let line = line.trim_end();
+        let line = line.strip_prefix("Result: ").unwrap_or(line);
+        if expected.is_multiline() {
+            line.replace('.', " ").trim_end().to_string()
+        } else {
+            line.to_string()
+        }
+    };
+    let expected: Vec<String> = expected.to_string().trim_end().lines().map(normalise).collect();
+    let mut actual: Vec<String> = String::from_utf8_lossy(stdout).lines().map(normalise).collect();
+    while actual.last().is_some_and(|line| line.trim().is_empty()) {
+        actual.pop();
+    }
+    !expected.is_empty() && actual.ends_with(&expected)
+}
+
+/// The last line a program printed, which is where most of the solutions put their
+/// answer, without any `Result: ` label.
+pub fn last_line(stdout: &[u8]) -> String {
+    let stdout = String::from_utf8_lossy(stdout);
+    let line = stdout.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("");
+    let line = line.trim();
+    line.strip_prefix("Result: ").unwrap_or(line).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_matches() {
+        assert!(output_matches(b"Some debugging\n157\n", &Answer::from(157)));
+        assert!(!output_matches(b"8\n(3, 2)\n", &Answer::from(8)));
+        assert!(!output_matches(b"157\nSome debugging\n", &Answer::from(157)));
+        assert!(output_matches(b"Result: CMZ\n", &Answer::from("CMZ")));
+        assert!(!output_matches(b"1570\n", &Answer::from(157)));
+        let art = Answer::Art(vec![String::from("# #"), String::from(" # ")]);
+        assert!(output_matches(b"13140\n#.#..\n.#.\n\n", &art));
+        assert!(!output_matches(b".#.\n", &art));
+        assert!(!output_matches(b"#.#..\n.#.\ndone\n", &art));
+    }
+
+    #[test]
+    fn test_output_with_timeout() {
+        let output = output_with_timeout(Command::new("sh").args(["-c", "echo 157"]), TIMEOUT)
+            .unwrap()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"157\n");
+
+        let started = Instant::now();
+        let limit = Duration::from_millis(100);
+        assert!(output_with_timeout(Command::new("sleep").arg("30"), limit).unwrap().is_none());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,911 @@
+mod answers;
+mod bench;
+mod days;
+mod differential;
+#[cfg(test)]
+mod examples;
+mod generate;
+mod inputs;
+mod languages;
+mod parity;
+#[cfg(test)]
+mod parser_props;
+mod pool;
+mod report;
+mod scaffold;
+mod stars;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use answers::{Answers, Check};
+use bench::{Baseline, Entry};
+use common::{AocError, AocResult, Answer, Part, RunArgs};
+use inputs::{InputStore, Kind};
+use report::{Format, Record};
+use languages::Language;
+use stars::StarTable;
+
+// Counts each thread's allocations, for the memory budget of `--memory`.
+#[global_allocator]
+static ALLOCATOR: common::budget::CountingAllocator = common::budget::CountingAllocator;
+
+const DEFAULT_INPUT_NAME: &str = "input.txt";
+const ANSWERS_FILE: &str = "answers.toml";
+const README_FILE: &str = "README.md";
+const PARTS: [Part; 2] = [Part::A, Part::B];
+const DEFAULT_ITERATIONS: usize = 5;
+const DEFAULT_THRESHOLD_PERCENT: f64 = 20.0;
+
+enum Command {
+    /// Run one or both parts of one day against one or more input files, or against the
+    /// day's puzzle input from the input store if none are given.
+    Run {
+        day: u32,
+        args: RunArgs,
+        format: Format,
+        budget: pool::Budget,
+    },
+    /// Run both parts of every registered day against `<day>/<input_name>`.
+    All {
+        input_name: String,
+        format: Format,
+        options: AllOptions,
+    },
+    /// Like `All`, but check the answers against `answers.toml`, optionally recording them.
+    Verify { input_name: String, update: bool },
+    /// Time one day (or every day, if `day` is `None`), optionally saving or comparing
+    /// against a JSON baseline.
+    Bench {
+        day: Option<u32>,
+        options: BenchOptions,
+    },
+    /// Manage the input store.
+    Inputs(InputsCommand),
+    /// Start a new day's crate from the template.
+    New(u32),
+    /// Run every language's solution for one day (or every day, if `day` is `None`) on
+    /// the same input and report where they disagree.
+    Parity { day: Option<u32>, input_name: String },
+    /// Print a random input for a day, `size` items big (or as big as a real input).
+    Generate {
+        day: u32,
+        seed: u64,
+        size: Option<usize>,
+    },
+    /// Compare each selected day's fast and reference solutions on `seeds` generated
+    /// inputs, `size` items big.
+    Differential {
+        day: Option<u32>,
+        seeds: u64,
+        size: usize,
+    },
+    /// Regenerate the README's star table by checking solutions against `answers.toml`.
+    Stars {
+        languages: Vec<Language>,
+        write: bool,
+    },
+}
+
+enum InputsCommand {
+    /// Copy the inputs in a directory or tarball into the store.
+    Import(PathBuf),
+    /// Download the puzzle input for one day (or every day, if `None`) unless it's stored.
+    Fetch(Option<u32>),
+    /// Print where a day's input would be read from.
+    Path { day: u32, kind: Kind },
+}
+
+struct AllOptions {
+    // How many parts to solve at once.
+    jobs: usize,
+    // How long each part may take, and how much memory it may hold on to.
+    budget: pool::Budget,
+}
+
+struct BenchOptions {
+    input_name: String,
+    iterations: usize,
+    save: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    // How much slower than the baseline's median counts as a regression, in percent.
+    threshold: f64,
+}
+
+fn usage(program: &str) -> String {
+    format!(
+        "Usage: {0} [--format text|json|csv] <day> <a|b|both> [input]... [--timeout SECONDS] [--memory MB] (- for stdin, default the stored input)\n       {0} [--format text|json|csv] all [input name, default {1}] [--jobs N] [--timeout SECONDS] [--memory MB]\n       {0} verify [--update] [input name, default {1}]\n       {0} bench <day|all> [--iterations N] [--input NAME] [--save FILE] [--compare FILE] [--threshold PERCENT]\n       {0} inputs import <dir|tarball>\n       {0} inputs fetch <day|all>\n       {0} inputs path <day> [input|example]\n       {0} new <day>\n       {0} parity <day|all> [input name, default {1}]\n       {0} generate <day> [--seed N] [--size N]\n       {0} differential <day|all> [--seeds N] [--size N]\n       {0} stars [--languages rust,python,go,scala|all] [--write]\nAny command takes -v for debug logging or -vv for step-by-step traces.",
+        program, DEFAULT_INPUT_NAME
+    )
+}
+
+fn parse_args(mut raw_args: Vec<String>) -> AocResult<Command> {
+    let program = raw_args.first().cloned().unwrap_or_else(|| String::from("aoc"));
+    let program = program.as_str();
+    let format = take_format(&mut raw_args)?;
+    let budget = take_budget(&mut raw_args)?;
+    match raw_args.get(1).map(|s| s.as_str()) {
+        Some("all") => {
+            let mut input_name = None;
+            let mut options = AllOptions { jobs: 1, budget };
+            let mut args = raw_args[2..].iter();
+            while let Some(arg) = args.next() {
+                if !arg.starts_with("--") {
+                    if input_name.is_some() {
+                        return Err(AocError::Usage(usage(program)));
+                    }
+                    input_name = Some(arg.clone());
+                    continue;
+                }
+                let value = args
+                    .next()
+                    .ok_or_else(|| AocError::Usage(format!("Missing value for {}", arg)))?;
+                let invalid = || AocError::Usage(format!("Invalid {}: {}", &arg[2..], value));
+                match arg.as_str() {
+                    "--jobs" => {
+                        options.jobs = value.parse().ok().filter(|n| *n > 0).ok_or_else(invalid)?;
+                    }
+                    _ => return Err(AocError::Usage(usage(program))),
+                }
+            }
+            Ok(Command::All {
+                input_name: input_name.unwrap_or_else(|| String::from(DEFAULT_INPUT_NAME)),
+                format: format.unwrap_or(Format::Text),
+                options,
+            })
+        }
+        Some("verify" | "bench" | "inputs" | "new" | "stars" | "parity" | "generate" | "differential")
+            if format.is_some() => Err(AocError::Usage(
+            String::from("--format only applies to running a day or all days"),
+        )),
+        Some("verify" | "bench" | "inputs" | "new" | "stars" | "parity" | "generate" | "differential")
+            if budget.is_limited() => Err(AocError::Usage(
+            String::from("--timeout and --memory only apply to running a day or all days"),
+        )),
+        Some("verify") => {
+            let mut update = false;
+            let mut input_name = None;
+            for arg in &raw_args[2..] {
+                match arg.as_str() {
+                    "--update" => update = true,
+                    name if input_name.is_none() => input_name = Some(name.to_string()),
+                    _ => return Err(AocError::Usage(usage(program))),
+                }
+            }
+            let input_name = input_name.unwrap_or_else(|| String::from(DEFAULT_INPUT_NAME));
+            Ok(Command::Verify { input_name, update })
+        }
+        Some("bench") if raw_args.len() >= 3 => {
+            let day = match raw_args[2].as_str() {
+                "all" => None,
+                day => Some(parse_day(day)?),
+            };
+            let options = parse_bench_options(&raw_args[3..])
+                .map_err(|e| AocError::Usage(format!("{}\n{}", e, usage(program))))?;
+            Ok(Command::Bench { day, options })
+        }
+        Some("inputs") => {
+            let command = match raw_args[2..].iter().map(|s| s.as_str()).collect::<Vec<_>>()[..] {
+                ["import", source] => InputsCommand::Import(PathBuf::from(source)),
+                ["fetch", "all"] => InputsCommand::Fetch(None),
+                ["fetch", day] => InputsCommand::Fetch(Some(parse_day(day)?)),
+                ["path", day] => InputsCommand::Path {
+                    day: parse_day(day)?,
+                    kind: Kind::Input,
+                },
+                ["path", day, kind] => InputsCommand::Path {
+                    day: parse_day(day)?,
+                    kind: Kind::from_arg(kind)
+                        .ok_or_else(|| AocError::Usage(format!("Invalid input kind: {}", kind)))?,
+                },
+                _ => return Err(AocError::Usage(usage(program))),
+            };
+            Ok(Command::Inputs(command))
+        }
+        Some("parity") if (3..=4).contains(&raw_args.len()) => {
+            let day = match raw_args[2].as_str() {
+                "all" => None,
+                day => Some(parse_day(day)?),
+            };
+            let input_name = raw_args
+                .get(3)
+                .cloned()
+                .unwrap_or_else(|| String::from(DEFAULT_INPUT_NAME));
+            Ok(Command::Parity { day, input_name })
+        }
+        Some("stars") => {
+            let mut languages = vec![Language::Rust];
+            let mut write = false;
+            let mut args = raw_args[2..].iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--write" => write = true,
+                    "--languages" => {
+                        let list = args
+                            .next()
+                            .ok_or_else(|| AocError::Usage(String::from("Missing value for --languages")))?;
+                        languages = parse_languages(list)?;
+                    }
+                    _ => return Err(AocError::Usage(usage(program))),
+                }
+            }
+            Ok(Command::Stars { languages, write })
+        }
+        Some("generate") if raw_args.len() >= 3 => {
+            let day = parse_day(&raw_args[2])?;
+            let (mut seed, mut size) = (0, None);
+            let mut args = raw_args[3..].iter();
+            while let Some(flag) = args.next() {
+                let value = args
+                    .next()
+                    .ok_or_else(|| AocError::Usage(format!("Missing value for {}", flag)))?;
+                let invalid = || AocError::Usage(format!("Invalid {}: {}", &flag[2..], value));
+                match flag.as_str() {
+                    "--seed" => seed = value.parse().map_err(|_| invalid())?,
+                    "--size" => size = Some(value.parse().map_err(|_| invalid())?),
+                    _ => return Err(AocError::Usage(usage(program))),
+                }
+            }
+            Ok(Command::Generate { day, seed, size })
+        }
+        Some("differential") if raw_args.len() >= 3 => {
+            let day = match raw_args[2].as_str() {
+                "all" => None,
+                day => Some(parse_day(day)?),
+            };
+            let (mut seeds, mut size) = (differential::DEFAULT_SEEDS, differential::DEFAULT_SIZE);
+            let mut args = raw_args[3..].iter();
+            while let Some(flag) = args.next() {
+                let value = args
+                    .next()
+                    .ok_or_else(|| AocError::Usage(format!("Missing value for {}", flag)))?;
+                let invalid = || AocError::Usage(format!("Invalid {}: {}", &flag[2..], value));
+                match flag.as_str() {
+                    "--seeds" => seeds = value.parse().map_err(|_| invalid())?,
+                    "--size" => size = value.parse().map_err(|_| invalid())?,
+                    _ => return Err(AocError::Usage(usage(program))),
+                }
+            }
+            Ok(Command::Differential { day, seeds, size })
+        }
+        Some("new") if raw_args.len() == 3 => Ok(Command::New(parse_day(&raw_args[2])?)),
+        Some(day) if raw_args.len() >= 3 => {
+            let day = parse_day(day)?;
+            let args = common::parse_run_args_without_input(&raw_args[2..])
+                .map_err(|e| AocError::Usage(format!("{}\n{}", e, usage(program))))?;
+            Ok(Command::Run {
+                day,
+                args,
+                format: format.unwrap_or(Format::Text),
+                budget,
+            })
+        }
+        _ => Err(AocError::Usage(usage(program))),
+    }
+}
+
+fn parse_day(day: &str) -> AocResult<u32> {
+    day.parse::<u32>()
+        .map_err(|_| AocError::Usage(format!("Invalid day: {}", day)))
+}
+
+/// Parse a comma-separated list of languages, or `all`.
+fn parse_languages(list: &str) -> AocResult<Vec<Language>> {
+    if list == "all" {
+        return Ok(Language::ALL.to_vec());
+    }
+    list.split(',')
+        .map(|name| {
+            Language::from_arg(name.trim())
+                .ok_or_else(|| AocError::Usage(format!("Unknown language: {}", name)))
+        })
+        .collect()
+}
+
+/// Remove `--timeout <seconds>` and `--memory <MB>` from anywhere in the arguments.
+fn take_budget(raw_args: &mut Vec<String>) -> AocResult<pool::Budget> {
+    let mut budget = pool::Budget::default();
+    while let Some(i) = raw_args.iter().position(|arg| arg == "--timeout" || arg == "--memory") {
+        let flag = &raw_args[i];
+        let value = raw_args
+            .get(i + 1)
+            .ok_or_else(|| AocError::Usage(format!("Missing value for {}", flag)))?;
+        let invalid = || AocError::Usage(format!("Invalid {}: {}", &flag[2..], value));
+        if flag == "--timeout" {
+            let seconds = value.parse().ok().filter(|s: &f64| *s > 0.0).ok_or_else(invalid)?;
+            budget.time = Some(Duration::try_from_secs_f64(seconds).map_err(|_| invalid())?);
+        } else {
+            let megabytes: usize = value.parse().ok().filter(|n| *n > 0).ok_or_else(invalid)?;
+            budget.memory = Some(megabytes.checked_mul(1024 * 1024).ok_or_else(invalid)?);
+        }
+        raw_args.drain(i..=i + 1);
+    }
+    Ok(budget)
+}
+
+/// Remove `--format <format>` from anywhere in the arguments.
+fn take_format(raw_args: &mut Vec<String>) -> AocResult<Option<Format>> {
+    let Some(i) = raw_args.iter().position(|arg| arg == "--format") else {
+        return Ok(None);
+    };
+    if i + 1 >= raw_args.len() {
+        return Err(AocError::Usage(String::from("Missing value for --format")));
+    }
+    let format = raw_args[i + 1].parse()?;
+    raw_args.drain(i..=i + 1);
+    Ok(Some(format))
+}
+
+fn parse_bench_options(args: &[String]) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions {
+        input_name: String::from(DEFAULT_INPUT_NAME),
+        iterations: DEFAULT_ITERATIONS,
+        save: None,
+        compare: None,
+        threshold: DEFAULT_THRESHOLD_PERCENT,
+    };
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", flag))?;
+        match flag.as_str() {
+            "--iterations" => {
+                options.iterations = value
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("Invalid iterations: {}", value))?;
+            }
+            "--input" => options.input_name = value.clone(),
+            "--save" => options.save = Some(PathBuf::from(value)),
+            "--compare" => options.compare = Some(PathBuf::from(value)),
+            "--threshold" => {
+                options.threshold = value
+                    .parse()
+                    .map_err(|_| format!("Invalid threshold: {}", value))?;
+            }
+            _ => return Err(format!("Unknown option: {}", flag)),
+        }
+    }
+    Ok(options)
+}
+
+/// The directory holding the `01`, `02`, ... day folders.
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn run_one(day: u32, mut args: RunArgs, format: Format, budget: pool::Budget) -> AocResult<()> {
+    let day = days::find(day)
+        .ok_or_else(|| AocError::Usage(format!("Day {} is not implemented", day)))?;
+    if args.input_files.is_empty() {
+        let path = InputStore::from_env(repo_root())?.resolve(day.number, Kind::Input)?;
+        args.input_files.push(path.to_string_lossy().into_owned());
+    }
+    if format == Format::Text {
+        if !budget.is_limited() {
+            return common::run_batch(day.solve, &args);
+        }
+        // Each part runs on its own under the budget, so it parses the input again.
+        let solve = day.solve;
+        return common::run_batch(
+            |contents: &str, parts: &[Part]| {
+                let contents = Arc::new(contents.to_string());
+                let tasks = parts.iter().map(|part| (Arc::clone(&contents), *part)).collect();
+                Ok(pool::run(tasks, 1, budget, move |(contents, part)| {
+                    solve(&contents, &[part])?.remove(0)
+                }))
+            },
+            &args,
+        );
+    }
+    let mut records = Vec::new();
+    for input_file in &args.input_files {
+        let contents = match common::read_input(input_file) {
+            Ok(contents) => contents,
+            Err(e) => {
+                records.extend(Record::from_timed(day.number, input_file, &args.parts, Err(e)));
+                continue;
+            }
+        };
+        if !budget.is_limited() {
+            let timed = (day.timed)(&contents, &args.parts);
+            records.extend(Record::from_timed(day.number, input_file, &args.parts, timed));
+            continue;
+        }
+        let contents = Arc::new(contents);
+        let tasks = args.parts.iter().map(|part| (day.timed, Arc::clone(&contents), *part)).collect();
+        let results = pool::run(tasks, 1, budget, |(timed, contents, part)| timed(&contents, &[part]));
+        for (part, timed) in args.parts.iter().zip(results) {
+            records.extend(Record::from_timed(day.number, input_file, &[*part], timed));
+        }
+    }
+    print_records(format, &records)
+}
+
+/// Print records in a machine-readable format, failing if any of them failed.
+fn print_records(format: Format, records: &[Record]) -> AocResult<()> {
+    print!("{}", report::render(format, records));
+    let failures = records.iter().filter(|r| r.result.is_err()).count();
+    if failures > 0 {
+        return Err(AocError::Solve(format!("{} part(s) failed", failures)));
+    }
+    Ok(())
+}
+
+/// Solve both parts of `day` against its `input_name` from the input store, or `None` if
+/// there's no such input.
+fn solve_day(
+    store: &InputStore,
+    day: &days::Day,
+    input_name: &str,
+) -> Option<AocResult<Vec<AocResult<Answer>>>> {
+    let contents = std::fs::read_to_string(store.find(day.number, input_name)?).ok()?;
+    Some((day.solve)(&contents, &PARTS))
+}
+
+/// Run everything, failing if any day that has an input couldn't be solved. Each part is
+/// a separate task, so with more than one job the slow days don't hold up the rest.
+fn run_all(input_name: &str, format: Format, options: &AllOptions) -> AocResult<()> {
+    let store = InputStore::from_env(repo_root())?;
+    let start = Instant::now();
+    // Every day and part, with the index of its task if the day has an input.
+    let mut planned: Vec<(u32, Part, Option<usize>)> = Vec::new();
+    let mut tasks: Vec<(days::TimedSolver, Arc<String>, Part)> = Vec::new();
+    for day in days::all() {
+        let contents = store.find(day.number, input_name).map(std::fs::read_to_string);
+        let contents = match contents {
+            Some(Ok(contents)) => Some(Arc::new(contents)),
+            _ => None,
+        };
+        for part in PARTS {
+            let task = contents.as_ref().map(|contents| {
+                tasks.push((day.timed, Arc::clone(contents), part));
+                tasks.len() - 1
+            });
+            planned.push((day.number, part, task));
+        }
+    }
+    let results = pool::run(tasks, options.jobs, options.budget, |(timed, contents, part)| {
+        timed(&contents, &[part])
+    });
+    let mut results: Vec<Option<AocResult<report::Timed>>> = results.into_iter().map(Some).collect();
+    let records: Vec<Option<Record>> = planned
+        .iter()
+        .map(|(day, part, task)| {
+            let timed = results[(*task)?].take().unwrap();
+            Record::from_timed(*day, input_name, &[*part], timed).pop()
+        })
+        .collect();
+
+    if format != Format::Text {
+        // Days without an input are left out.
+        let records: Vec<Record> = records.into_iter().flatten().collect();
+        return print_records(format, &records);
+    }
+    let mut rows: Vec<(u32, Part, String)> = Vec::new();
+    let (mut solved, mut failures, mut timeouts, mut over_budget) = (0, 0, 0, 0);
+    for ((day, part, _), record) in planned.iter().zip(records) {
+        let result = match record.map(|record| record.result) {
+            None => String::from("missing input"),
+            Some(Ok(answer)) => {
+                solved += 1;
+                answer.to_string()
+            }
+            Some(Err(e)) => {
+                match e {
+                    AocError::TimedOut(_) => timeouts += 1,
+                    AocError::OverBudget(_) => over_budget += 1,
+                    _ => failures += 1,
+                }
+                format!("error: {}", e)
+            }
+        };
+        rows.push((*day, *part, result));
+    }
+    print!("{}", results_table(&rows));
+    println!(
+        "{} solved, {} failed, {} timed out, {} over budget in {} ({} job(s))",
+        solved,
+        failures,
+        timeouts,
+        over_budget,
+        bench::format_duration(start.elapsed()),
+        options.jobs
+    );
+    let failed = failures + timeouts + over_budget;
+    if failed > 0 {
+        return Err(AocError::Solve(format!("{} part(s) failed", failed)));
+    }
+    Ok(())
+}
+
+/// Run everything and compare against the recorded answers. With `update`, new and changed
+/// answers are written back to the answers file instead of counting as failures.
+fn verify(input_name: &str, update: bool) -> AocResult<()> {
+    let answers_path = repo_root().join(ANSWERS_FILE);
+    let mut answers = Answers::load(&answers_path)?;
+    let store = InputStore::from_env(repo_root())?;
+    let mut rows: Vec<(u32, Part, String)> = Vec::new();
+    let (mut passed, mut mismatched, mut failed, mut new) = (0, 0, 0, 0);
+    for day in days::all() {
+        let results = match solve_day(&store, &day, input_name) {
+            None => {
+                for part in PARTS {
+                    rows.push((day.number, part, String::from("missing input")));
+                }
+                continue;
+            }
+            Some(Err(e)) => PARTS.iter().map(|_| Err(e.clone())).collect(),
+            Some(Ok(results)) => results,
+        };
+        for (part, result) in PARTS.into_iter().zip(results) {
+            let answer = match result {
+                Ok(answer) => answer,
+                Err(e) => {
+                    failed += 1;
+                    rows.push((day.number, part, format!("FAIL: {}", e)));
+                    continue;
+                }
+            };
+            let status = match answers.check(day.number, input_name, part, &answer) {
+                Check::Pass => {
+                    passed += 1;
+                    with_answer("pass", &answer)
+                }
+                Check::Mismatch { expected } => {
+                    let status = if update { "updated" } else { "MISMATCH" };
+                    mismatched += 1;
+                    format!("{}\n{}", status, answers::diff(&expected, &answer))
+                }
+                Check::Unrecorded => {
+                    new += 1;
+                    with_answer(if update { "recorded" } else { "new" }, &answer)
+                }
+            };
+            if update {
+                answers.record(day.number, input_name, part, answer);
+            }
+            rows.push((day.number, part, status));
+        }
+    }
+    print!("{}", results_table(&rows));
+    println!(
+        "{} passed, {} mismatched, {} failed, {} {}",
+        passed,
+        mismatched,
+        failed,
+        new,
+        if update { "recorded" } else { "unrecorded" }
+    );
+    if update {
+        answers.save(&answers_path)?;
+    }
+    if failed > 0 || (mismatched > 0 && !update) {
+        return Err(AocError::Solve(String::from("verification failed")));
+    }
+    Ok(())
+}
+
+/// Time parsing and each part of the selected days, then report min/median/max and, if
+/// there's a baseline, how the median has changed. Regressions beyond the threshold fail.
+fn run_bench(day: Option<u32>, options: &BenchOptions) -> AocResult<()> {
+    let selected: Vec<days::Day> = match day {
+        None => days::all(),
+        Some(number) => vec![days::find(number)
+            .ok_or_else(|| AocError::Usage(format!("Day {} is not implemented", number)))?],
+    };
+    let store = InputStore::from_env(repo_root())?;
+    let baseline = match &options.compare {
+        Some(path) => Some(Baseline::load(path)?),
+        None => None,
+    };
+
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut table = String::from(
+        "| Day | Stage |       Min |    Median |       Max |  Baseline | Change\n| --- | ----- | --------- | --------- | --------- | --------- | ------\n",
+    );
+    let mut regressions = 0;
+    let mut failures = 0;
+    for day in selected {
+        let input_path = store.find(day.number, &options.input_name);
+        let Some(Ok(contents)) = input_path.map(std::fs::read_to_string) else {
+            table.push_str(&format!("| {: >3} |       | missing input\n", day.number));
+            continue;
+        };
+        let timings = match (day.bench)(&contents, options.iterations) {
+            Ok(timings) => timings,
+            Err(e) => {
+                failures += 1;
+                table.push_str(&format!("| {: >3} | parse | error: {}\n", day.number, e));
+                continue;
+            }
+        };
+        let mut stages = vec![(String::from("parse"), Ok(timings.parse))];
+        stages.extend(timings.parts.into_iter().map(|(part, stats)| (part.to_string(), stats)));
+        for (stage, stats) in stages {
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(e) => {
+                    failures += 1;
+                    table.push_str(&format!("| {: >3} | {: >5} | error: {}\n", day.number, stage, e));
+                    continue;
+                }
+            };
+            let entry = Entry::new(day.number, &stage, &stats);
+            let previous = baseline.as_ref().and_then(|b| b.find(day.number, &stage));
+            let (baseline_text, change_text) = match previous {
+                None => (String::new(), String::new()),
+                Some(previous) => {
+                    let change = bench::change(previous, &entry) * 100.0;
+                    let mut text = format!("{:+.1}%", change);
+                    if change > options.threshold {
+                        regressions += 1;
+                        text.push_str(" REGRESSION");
+                    }
+                    let median = std::time::Duration::from_nanos(previous.median_ns);
+                    (bench::format_duration(median), text)
+                }
+            };
+            table.push_str(&format!(
+                "| {: >3} | {: >5} | {: >9} | {: >9} | {: >9} | {: >9} | {}\n",
+                day.number,
+                stage,
+                bench::format_duration(stats.min),
+                bench::format_duration(stats.median),
+                bench::format_duration(stats.max),
+                baseline_text,
+                change_text,
+            ));
+            entries.push(entry);
+        }
+    }
+    print!("{}", table);
+
+    if let Some(path) = &options.save {
+        let baseline = Baseline {
+            input: options.input_name.clone(),
+            iterations: options.iterations,
+            entries,
+        };
+        baseline.save(path)?;
+        println!("Saved baseline to {}", path.display());
+    }
+    if regressions > 0 || failures > 0 {
+        return Err(AocError::Solve(format!(
+            "{} regression(s), {} failure(s)",
+            regressions, failures
+        )));
+    }
+    Ok(())
+}
+
+fn run_inputs(command: InputsCommand) -> AocResult<()> {
+    let store = InputStore::from_env(repo_root())?;
+    match command {
+        InputsCommand::Import(source) => {
+            let imported = store.import(&source)?;
+            for path in &imported {
+                println!("{}", path.display());
+            }
+            println!("Imported {} input(s) into {}", imported.len(), store.cache_dir.display());
+        }
+        InputsCommand::Fetch(day) => {
+            if store.fetcher.is_none() {
+                return Err(AocError::Usage(String::from(
+                    "Fetching needs a session token, in AOC_SESSION or the cache's session file",
+                )));
+            }
+            let days: Vec<u32> = match day {
+                Some(day) => vec![day],
+                None => days::all().iter().map(|d| d.number).collect(),
+            };
+            for day in days {
+                println!("{}", store.resolve(day, Kind::Input)?.display());
+            }
+        }
+        InputsCommand::Path { day, kind } => {
+            let path = store
+                .find(day, kind.file_name())
+                .unwrap_or_else(|| store.path(store.year, day, kind));
+            println!("{}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Compare the languages' answers for each selected day, failing if any disagree.
+fn run_parity(day: Option<u32>, input_name: &str) -> AocResult<()> {
+    let root = repo_root();
+    let store = InputStore::from_env(root.clone())?;
+    let selected: Vec<u32> = match day {
+        Some(day) => vec![day],
+        // Every day folder, whichever languages it has.
+        None => (1..=25)
+            .filter(|day| root.join(format!("{:02}", day)).is_dir())
+            .collect(),
+    };
+    let mut rows: Vec<(u32, Part, String)> = Vec::new();
+    let mut disagreements = 0;
+    for day in selected {
+        let Some(input) = store.find(day, input_name) else {
+            for part in PARTS {
+                rows.push((day, part, String::from("missing input")));
+            }
+            continue;
+        };
+        for comparison in parity::compare(&root, day, &input)? {
+            if comparison.disagrees() {
+                disagreements += 1;
+            }
+            rows.push((day, comparison.part, comparison.summary()));
+        }
+    }
+    print!("{}", results_table(&rows));
+    if disagreements > 0 {
+        return Err(AocError::Solve(format!("{} part(s) disagree", disagreements)));
+    }
+    Ok(())
+}
+
+/// Compare fast and reference solutions on generated inputs, failing if any disagree.
+fn run_differential(day: Option<u32>, seeds: u64, size: usize) -> AocResult<()> {
+    let selected: Vec<days::Day> = match day {
+        None => days::all(),
+        Some(number) => vec![days::find(number)
+            .ok_or_else(|| AocError::Usage(format!("Day {} is not implemented", number)))?],
+    };
+    let mut rows: Vec<(u32, Part, String)> = Vec::new();
+    let mut disagreements = 0;
+    for day in selected {
+        let Some(generator) = generate::find(day.number) else {
+            for part in PARTS {
+                rows.push((day.number, part, String::from("no input generator")));
+            }
+            continue;
+        };
+        for (part, outcome) in differential::compare(&day, &generator, seeds, size) {
+            if let differential::Outcome::Disagrees { .. } = outcome {
+                disagreements += 1;
+            }
+            rows.push((day.number, part, outcome.summary(day.number, size)));
+        }
+    }
+    print!("{}", results_table(&rows));
+    if disagreements > 0 {
+        return Err(AocError::Solve(format!("{} part(s) disagree", disagreements)));
+    }
+    Ok(())
+}
+
+/// Check each language's solutions and print the README's star table with the results,
+/// or write it back into the README.
+fn run_stars(languages: &[Language], write: bool) -> AocResult<()> {
+    let readme_path = repo_root().join(README_FILE);
+    let readme = std::fs::read_to_string(&readme_path)?;
+    let (mut table, lines) = StarTable::parse(&readme)
+        .ok_or_else(|| AocError::Solve(format!("No star table in {}", README_FILE)))?;
+    let answers = Answers::load(&repo_root().join(ANSWERS_FILE))?;
+    let store = InputStore::from_env(repo_root())?;
+    for language in languages {
+        stars::check(&mut table, *language, &answers, &store)?;
+    }
+    if !write {
+        print!("{}", table.render());
+        return Ok(());
+    }
+    let readme_lines: Vec<&str> = readme.lines().collect();
+    let mut updated = readme_lines[..lines.start].join("\n");
+    updated.push('\n');
+    updated.push_str(&table.render());
+    for line in &readme_lines[lines.end..] {
+        updated.push_str(line);
+        updated.push('\n');
+    }
+    std::fs::write(&readme_path, updated)?;
+    println!("Updated the star table in {}", README_FILE);
+    Ok(())
+}
+
+/// A status followed by the answer, on its own lines if it's multi-line.
+fn with_answer(status: &str, answer: &Answer) -> String {
+    if answer.is_multiline() {
+        format!("{}\n{}", status, answer)
+    } else {
+        format!("{} {}", status, answer)
+    }
+}
+
+/// Render results as a markdown-style table, like the one in the README.
+/// Multi-line answers continue on their own rows beneath the first line.
+fn results_table(rows: &[(u32, Part, String)]) -> String {
+    let mut table = String::from("| Day | Part | Answer\n| --- | ---- | ------\n");
+    for (day, part, answer) in rows {
+        let mut lines = answer.trim_matches('\n').lines();
+        let first = lines.next().unwrap_or("");
+        table.push_str(&format!("| {: >3} | {: >4} | {}\n", day, part, first));
+        for line in lines {
+            table.push_str(&format!("|     |      | {}\n", line));
+        }
+    }
+    table
+}
+
+fn run(args: std::env::Args) -> AocResult<()> {
+    let mut raw_args: Vec<String> = args.collect();
+    common::init_logging(common::take_verbosity(&mut raw_args));
+    match parse_args(raw_args)? {
+        Command::Run { day, args, format, budget } => run_one(day, args, format, budget),
+        Command::All {
+            input_name,
+            format,
+            options,
+        } => run_all(&input_name, format, &options),
+        Command::Verify { input_name, update } => verify(&input_name, update),
+        Command::Bench { day, options } => run_bench(day, &options),
+        Command::Inputs(command) => run_inputs(command),
+        Command::Parity { day, input_name } => run_parity(day, &input_name),
+        Command::Stars { languages, write } => run_stars(&languages, write),
+        Command::Differential { day, seeds, size } => run_differential(day, seeds, size),
+        Command::Generate { day, seed, size } => {
+            let generator = generate::find(day)
+                .ok_or_else(|| AocError::Usage(format!("Day {} has no input generator", day)))?;
+            let size = size.unwrap_or(generator.default_size);
+            print!("{}", (generator.generate)(&mut common::Rng::new(seed), size));
+            Ok(())
+        }
+        Command::New(day) => {
+            let root = repo_root();
+            for path in scaffold::new_day(&root, day)? {
+                println!("{}", path.strip_prefix(&root).unwrap_or(&path).display());
+            }
+            println!("Paste the example into {:02}/test_input.txt to start testing.", day);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: std::env::Args = std::env::args();
+    common::exit(run(args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_budget() {
+        let mut args: Vec<String> = ["aoc", "16", "--memory", "500", "b", "--timeout", "1.5"]
+            .map(String::from)
+            .to_vec();
+        let budget = take_budget(&mut args).unwrap();
+        assert_eq!(args, ["aoc", "16", "b"]);
+        assert_eq!(budget.time, Some(Duration::from_millis(1500)));
+        assert_eq!(budget.memory, Some(500 * 1024 * 1024));
+
+        let mut args = vec![String::from("--memory"), usize::MAX.to_string()];
+        assert!(matches!(take_budget(&mut args), Err(AocError::Usage(_))));
+        let mut args = vec![String::from("--timeout")];
+        assert!(matches!(take_budget(&mut args), Err(AocError::Usage(_))));
+        assert!(!take_budget(&mut vec![]).unwrap().is_limited());
+    }
+
+    #[test]
+    fn test_results_table() {
+        let rows = vec![
+            (6, Part::A, String::from("7")),
+            (10, Part::B, String::from("\n##\n #\n")),
+        ];
+        let expected = "| Day | Part | Answer
+| --- | ---- | ------
+|   6 |    a | 7
+|  10 |    b | ##
+|     |      |  #
+";
+        assert_eq!(results_table(&rows), expected);
+    }
+}
@@ -0,0 +1,194 @@
+use std::path::Path;
+
+use common::{AocError, AocResult, Answer, Part};
+
+use crate::days;
+use crate::languages::{self, Language};
+
+const PARTS: [Part; 2] = [Part::A, Part::B];
+// Rust goes first, so that its answer is the one the others are held to.
+const ORDER: [Language; 4] = [Language::Rust, Language::Python, Language::Go, Language::Scala];
+
+/// What one language's solution made of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// An answer that agrees with the others (or the first one given).
+    Agrees(String),
+    /// Something other than the answer the others agreed on: the last line it printed.
+    Disagrees(String),
+    /// The solution crashed or returned an error.
+    Failed(String),
+    /// The solution was still running when its time ran out (see `languages::TIMEOUT`).
+    TimedOut,
+    /// There's no solution in this language.
+    Missing,
+    /// The language's toolchain isn't installed here.
+    Unavailable,
+}
+
+/// Every language's outcome for one part.
+#[derive(Debug)]
+pub struct Comparison {
+    pub part: Part,
+    pub outcomes: Vec<(Language, Outcome)>,
+}
+
+impl Comparison {
+    pub fn disagrees(&self) -> bool {
+        self.outcomes
+            .iter()
+            .any(|(_, outcome)| matches!(outcome, Outcome::Disagrees(_)))
+    }
+
+    /// One line per comparison, e.g. `Rust 24000, Python 24000, Go differs: 23000`.
+    /// Languages with no solution are left out.
+    pub fn summary(&self) -> String {
+        let outcomes: Vec<String> = self
+            .outcomes
+            .iter()
+            .filter(|(_, outcome)| *outcome != Outcome::Missing)
+            .map(|(language, outcome)| {
+                let name = language.name();
+                match outcome {
+                    Outcome::Agrees(answer) => format!("{} {}", name, answer),
+                    Outcome::Disagrees(output) => format!("{} differs: {}", name, output),
+                    Outcome::Failed(e) => format!("{} failed: {}", name, e),
+                    Outcome::TimedOut => format!("{} timed out", name),
+                    Outcome::Missing => unreachable!(),
+                    Outcome::Unavailable => format!("{} can't run here", name),
+                }
+            })
+            .collect();
+        let summary = if outcomes.is_empty() {
+            String::from("no solutions")
+        } else {
+            outcomes.join(", ")
+        };
+        if self.disagrees() {
+            format!("DISAGREE: {}", summary)
+        } else {
+            summary
+        }
+    }
+}
+
+/// Run every language's solution for both parts of `day` against the same input.
+pub fn compare(root: &Path, day: u32, input: &Path) -> AocResult<Vec<Comparison>> {
+    let contents = std::fs::read_to_string(input)?;
+    let input = std::fs::canonicalize(input)?;
+    let rust: Option<Vec<AocResult<Answer>>> = days::find(day).map(|solver| {
+        (solver.solve)(&contents, &PARTS).unwrap_or_else(|e| vec![Err(e); PARTS.len()])
+    });
+    let mut comparisons = Vec::new();
+    for (i, part) in PARTS.into_iter().enumerate() {
+        let mut reference: Option<Answer> = None;
+        let mut outcomes = Vec::new();
+        for language in ORDER {
+            let outcome = if language == Language::Rust {
+                match rust.as_ref().map(|answers| &answers[i]) {
+                    None | Some(Err(AocError::Unimplemented)) => Outcome::Missing,
+                    Some(Err(e)) => Outcome::Failed(e.to_string()),
+                    Some(Ok(answer)) => {
+                        reference = Some(answer.clone());
+                        Outcome::Agrees(display(answer))
+                    }
+                }
+            } else {
+                match languages::external_command(root, language, day, part, &input) {
+                    None => Outcome::Missing,
+                    Some(mut command) => match languages::output_with_timeout(&mut command, languages::TIMEOUT) {
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Outcome::Unavailable,
+                        Err(e) => Outcome::Failed(e.to_string()),
+                        Ok(None) => Outcome::TimedOut,
+                        Ok(Some(output)) => {
+                            judge(&mut reference, output.status.success(), &output.stdout)
+                        }
+                    },
+                }
+            };
+            outcomes.push((language, outcome));
+        }
+        comparisons.push(Comparison { part, outcomes });
+    }
+    Ok(comparisons)
+}
+
+/// Compare a program's output with the reference answer. If there isn't one yet, the last
+/// line it printed becomes the reference.
+fn judge(reference: &mut Option<Answer>, success: bool, stdout: &[u8]) -> Outcome {
+    let last_line = languages::last_line(stdout);
+    if !success {
+        return Outcome::Failed(last_line);
+    }
+    match reference {
+        Some(answer) if languages::output_matches(stdout, answer) => {
+            Outcome::Agrees(display(answer))
+        }
+        Some(_) => Outcome::Disagrees(last_line),
+        None => {
+            let answer = match last_line.parse::<i64>() {
+                Ok(n) => Answer::Int(n),
+                Err(_) => Answer::Text(last_line.clone()),
+            };
+            *reference = Some(answer);
+            Outcome::Agrees(last_line)
+        }
+    }
+}
+
+/// An answer short enough for a summary line.
+fn display(answer: &Answer) -> String {
+    if answer.is_multiline() {
+        String::from("(picture)")
+    } else {
+        answer.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_judge() {
+        let mut reference = None;
+        let agrees = Outcome::Agrees(String::from("24000"));
+        assert_eq!(judge(&mut reference, true, b"debug\n24000\n"), agrees);
+        assert_eq!(reference, Some(Answer::Int(24000)));
+        assert_eq!(judge(&mut reference, true, b"Result: 24000\n"), agrees);
+        let disagrees = Outcome::Disagrees(String::from("23000"));
+        assert_eq!(judge(&mut reference, true, b"23000\n"), disagrees);
+        // Only the last line counts, so a stray debug print after the answer disagrees.
+        let debug = Outcome::Disagrees(String::from("done"));
+        assert_eq!(judge(&mut reference, true, b"24000\ndone\n"), debug);
+        let failed = Outcome::Failed(String::from("Error: boom"));
+        assert_eq!(judge(&mut reference, false, b"Error: boom\n"), failed);
+    }
+
+    #[test]
+    fn test_summary() {
+        let comparison = Comparison {
+            part: Part::A,
+            outcomes: vec![
+                (Language::Rust, Outcome::Agrees(String::from("157"))),
+                (Language::Python, Outcome::Disagrees(String::from("158"))),
+                (Language::Go, Outcome::Missing),
+                (Language::Scala, Outcome::Unavailable),
+            ],
+        };
+        assert!(comparison.disagrees());
+        assert_eq!(
+            comparison.summary(),
+            "DISAGREE: Rust 157, Python differs: 158, Scala can't run here"
+        );
+        let comparison = Comparison {
+            part: Part::B,
+            outcomes: vec![
+                (Language::Rust, Outcome::Agrees(String::from("157"))),
+                (Language::Python, Outcome::TimedOut),
+            ],
+        };
+        assert!(!comparison.disagrees());
+        assert_eq!(comparison.summary(), "Rust 157, Python timed out");
+    }
+}
@@ -0,0 +1,171 @@
+//! Property tests for every day's parser: whatever the input, parsing must return an
+//! answer or an error, never panic. The inputs are each day's example with random edits
+//! (near-valid, so they get past the first few checks), including swapping its numbers for
+//! extreme ones, and random text.
+
+use proptest::prelude::*;
+
+use crate::days;
+
+/// One change to an example input. Positions wrap around the input's length.
+#[derive(Debug, Clone)]
+enum Edit {
+    Delete(usize),
+    Insert(usize, char),
+    Replace(usize, char),
+    Truncate(usize),
+    DuplicateLine(usize),
+    DeleteLine(usize),
+    /// Replace one of the input's numbers (with its sign, if any) with another.
+    Number(usize, String),
+}
+
+/// Characters that mean something to at least one parser, plus anything at all.
+fn interesting_char() -> impl Strategy<Value = char> {
+    prop_oneof![
+        3 => proptest::sample::select(" \n,-=:;[]0123456789abcxyzABCXYZRLUD$/".chars().collect::<Vec<_>>()),
+        1 => any::<char>(),
+    ]
+}
+
+/// Numbers at and past the edges of the integer types parsers use, and long ones.
+fn extreme_number() -> impl Strategy<Value = String> {
+    let edges = [
+        i32::MIN as i128,
+        i32::MIN as i128 + 1,
+        i32::MAX as i128,
+        i32::MAX as i128 + 1,
+        u32::MAX as i128,
+        i64::MIN as i128,
+        i64::MAX as i128,
+        u64::MAX as i128,
+        u64::MAX as i128 + 1,
+    ];
+    prop_oneof![
+        proptest::sample::select(edges.to_vec()).prop_map(|n| n.to_string()),
+        (any::<bool>(), "[1-9][0-9]{9,20}").prop_map(|(negative, digits)| {
+            if negative { format!("-{}", digits) } else { digits }
+        }),
+    ]
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    prop_oneof![
+        (any::<usize>()).prop_map(Edit::Delete),
+        (any::<usize>(), interesting_char()).prop_map(|(i, c)| Edit::Insert(i, c)),
+        (any::<usize>(), interesting_char()).prop_map(|(i, c)| Edit::Replace(i, c)),
+        (any::<usize>()).prop_map(Edit::Truncate),
+        (any::<usize>()).prop_map(Edit::DuplicateLine),
+        (any::<usize>()).prop_map(Edit::DeleteLine),
+        (any::<usize>(), extreme_number()).prop_map(|(i, n)| Edit::Number(i, n)),
+    ]
+}
+
+fn apply(text: &str, edit: &Edit) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    let mut lines: Vec<&str> = text.lines().collect();
+    let wrap = |i: usize, len: usize| if len == 0 { 0 } else { i % len };
+    match *edit {
+        Edit::Delete(i) if !chars.is_empty() => {
+            chars.remove(wrap(i, chars.len()));
+        }
+        Edit::Insert(i, c) => chars.insert(wrap(i, chars.len() + 1), c),
+        Edit::Replace(i, c) if !chars.is_empty() => {
+            let i = wrap(i, chars.len());
+            chars[i] = c;
+        }
+        Edit::Truncate(i) => chars.truncate(wrap(i, chars.len() + 1)),
+        Edit::DuplicateLine(i) if !lines.is_empty() => {
+            let i = wrap(i, lines.len());
+            lines.insert(i, lines[i]);
+            return lines.join("\n");
+        }
+        Edit::DeleteLine(i) if !lines.is_empty() => {
+            lines.remove(wrap(i, lines.len()));
+            return lines.join("\n");
+        }
+        Edit::Number(i, ref n) => {
+            let numbers = numbers(text);
+            if let Some(&(start, end)) = numbers.get(wrap(i, numbers.len())) {
+                return format!("{}{}{}", &text[..start], n, &text[end..]);
+            }
+        }
+        _ => {}
+    }
+    chars.into_iter().collect()
+}
+
+/// Where each number in `text` starts and ends, in bytes, counting a `-` just before it.
+fn numbers(text: &str) -> Vec<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() {
+            let start = if i > 0 && bytes[i - 1] == b'-' { i - 1 } else { i };
+            let mut end = i;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+            numbers.push((start, end));
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    numbers
+}
+
+/// Each registered day's example input, by position in `days::all()`.
+fn examples() -> Vec<String> {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    days::all()
+        .iter()
+        .map(|day| {
+            let path = root
+                .join(format!("{:02}", day.number))
+                .join("test_input.txt");
+            std::fs::read_to_string(path).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Parse only: solving with no parts still parses the input.
+fn parse(day_index: usize, input: &str) {
+    let day = &days::all()[day_index];
+    let _ = (day.solve)(input, &[]);
+}
+
+proptest! {
+    #[test]
+    fn parsers_survive_edited_examples(
+        day_index in 0..days::all().len(),
+        edits in proptest::collection::vec(edit(), 1..6),
+    ) {
+        let mut input = examples()[day_index].clone();
+        for edit in &edits {
+            input = apply(&input, edit);
+        }
+        parse(day_index, &input);
+    }
+
+    #[test]
+    fn parsers_survive_random_text(
+        day_index in 0..days::all().len(),
+        input in proptest::collection::vec(interesting_char(), 0..200),
+    ) {
+        parse(day_index, &input.into_iter().collect::<String>());
+    }
+}
+
+#[test]
+fn test_apply() {
+    assert_eq!(apply("ab\ncd", &Edit::Delete(4)), "ab\nc");
+    assert_eq!(apply("ab\ncd", &Edit::Insert(3, 'x')), "ab\nxcd");
+    assert_eq!(apply("ab\ncd", &Edit::Truncate(2)), "ab");
+    assert_eq!(apply("ab\ncd", &Edit::DuplicateLine(1)), "ab\ncd\ncd");
+    assert_eq!(apply("ab\ncd", &Edit::DeleteLine(2)), "cd");
+    assert_eq!(apply("x=1, y=-23", &Edit::Number(1, "2147483647".into())), "x=1, y=2147483647");
+    assert_eq!(apply("x=1, y=-23", &Edit::Number(2, "-9".into())), "x=-9, y=-23");
+    assert_eq!(apply("no numbers", &Edit::Number(0, "7".into())), "no numbers");
+}
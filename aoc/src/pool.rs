@@ -0,0 +1,194 @@
+use std::any::Any;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use common::budget::{self, CancellationToken};
+use common::{AocError, AocResult};
+
+// Solvers can recurse as deeply here as they would on the main thread.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+// How long a cancelled task gets to reach a checkpoint and unwind, freeing its memory.
+const GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// How much time and memory each task gets. `None` means no limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Budget {
+    pub time: Option<Duration>,
+    /// In bytes.
+    pub memory: Option<usize>,
+}
+
+impl Budget {
+    pub fn is_limited(&self) -> bool {
+        self.time.is_some() || self.memory.is_some()
+    }
+}
+
+/// Run `work` on each task, using up to `jobs` threads at a time, and return the results in
+/// the tasks' order. Each task runs under the budget (see `common::budget`): one that uses
+/// too much memory fails at its next checkpoint, and one still running when its time is up
+/// is cancelled and reported as timed out. A task that never reaches a checkpoint can't be
+/// stopped, so it runs on in the background until it finishes or the process exits, but
+/// its worker moves on. A task that panics is reported as failed.
+pub fn run<T, R, F>(tasks: Vec<T>, jobs: usize, limits: Budget, work: F) -> Vec<AocResult<R>>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> AocResult<R> + Copy + Send + 'static,
+{
+    let count = tasks.len();
+    let queue = Arc::new(Mutex::new(tasks.into_iter().enumerate()));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..jobs.clamp(1, count.max(1)) {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        thread::spawn(move || loop {
+            let Some((i, task)) = queue.lock().unwrap().next() else {
+                break;
+            };
+            if sender.send((i, run_task(task, limits, work))).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+
+    let mut results: Vec<Option<AocResult<R>>> = (0..count).map(|_| None).collect();
+    for (i, result) in receiver {
+        results[i] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err(AocError::solve("worker thread died"))))
+        .collect()
+}
+
+/// Run one task on a thread of its own, so that we can stop waiting for it.
+fn run_task<T, R, F>(task: T, limits: Budget, work: F) -> AocResult<R>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> AocResult<R> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let token = CancellationToken::new();
+    let solver_token = token.clone();
+    let handle = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let result = budget::with_budget(solver_token, limits.memory, || work(task));
+            let _ = sender.send(result);
+        })?;
+    let received = match limits.time {
+        Some(limit) => receiver.recv_timeout(limit),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            let error = AocError::TimedOut(limits.time.unwrap());
+            token.cancel(error.clone());
+            let _ = receiver.recv_timeout(GRACE_PERIOD);
+            Err(error)
+        }
+        // The thread ended without sending anything, so it must have panicked.
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => Err(AocError::Solve(format!("panicked: {}", panic_message(&*payload)))),
+            Ok(()) => Err(AocError::solve("solver thread ended without an answer")),
+        },
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_keep_task_order() {
+        // Later tasks finish first.
+        let tasks: Vec<u64> = (0..8).rev().collect();
+        let results = run(tasks, 4, Budget::default(), |n| {
+            thread::sleep(Duration::from_millis(n * 5));
+            Ok(n * 10)
+        });
+        let expected: Vec<AocResult<u64>> = (0..8).rev().map(|n| Ok(n * 10)).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_timeouts_and_panics() {
+        // Generous, so that the tasks which finish at once still do on a loaded machine.
+        let limit = Duration::from_secs(1);
+        let limits = Budget { time: Some(limit), memory: None };
+        let results = run(vec![0, 1, 2, 3], 2, limits, |n| match n {
+            // Runs until it's cancelled, rather than leaving a thread behind.
+            1 => loop {
+                budget::checkpoint()?;
+                thread::sleep(Duration::from_millis(1));
+            },
+            2 => panic!("boom"),
+            3 => Err(AocError::NoAnswer),
+            _ => Ok(n),
+        });
+        assert_eq!(
+            results,
+            vec![
+                Ok(0),
+                Err(AocError::TimedOut(limit)),
+                Err(AocError::solve("panicked: boom")),
+                Err(AocError::NoAnswer),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_tasks() {
+        let results: Vec<AocResult<u32>> = run(Vec::<u32>::new(), 4, Budget::default(), Ok);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_tasks_stop_at_a_checkpoint() {
+        let limit = Duration::from_millis(50);
+        let limits = Budget { time: Some(limit), memory: None };
+        let (sender, receiver) = mpsc::channel();
+        let results = run(vec![sender], 1, limits, |sender| {
+            loop {
+                if let Err(e) = budget::checkpoint() {
+                    let _ = sender.send(e.clone());
+                    return Err::<(), _>(e);
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        assert_eq!(results, vec![Err(AocError::TimedOut(limit))]);
+        // The solver saw the cancellation itself.
+        assert_eq!(receiver.recv_timeout(GRACE_PERIOD), Ok(AocError::TimedOut(limit)));
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let limits = Budget { time: None, memory: Some(1 << 20) };
+        let results = run(vec![1 << 10, 1 << 22], 2, limits, |size: usize| {
+            let mut hoard: Vec<Vec<u8>> = Vec::new();
+            for _ in 0..16 {
+                hoard.push(vec![1; size]);
+                budget::checkpoint()?;
+            }
+            Ok(hoard.len())
+        });
+        assert_eq!(results, vec![Ok(16), Err(AocError::OverBudget(1 << 20))]);
+    }
+}
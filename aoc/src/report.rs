@@ -0,0 +1,214 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use common::{AocError, AocResult, Answer, Part, Solution};
+use serde_json::{json, Value};
+
+/// How the runner reports its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The human-readable output: `Your answer is ...`, or a table for `all`.
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// A header row, then one row per record.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Format, AocError> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(AocError::Usage(format!("Invalid format: {}", s))),
+        }
+    }
+}
+
+/// How long parsing took, and each part's answer with how long it took to solve.
+pub struct Timed {
+    pub parse: Duration,
+    pub parts: Vec<(AocResult<Answer>, Duration)>,
+}
+
+/// Parse `contents` once, then solve each of `parts`, timing every step.
+pub fn timed<S: Solution>(contents: &str, parts: &[Part]) -> AocResult<Timed> {
+    let start = Instant::now();
+    let input = S::parse(contents)?;
+    let parse = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let result = S::solve_part(&input, *part);
+            (result, start.elapsed())
+        })
+        .collect();
+    Ok(Timed { parse, parts })
+}
+
+/// The outcome of running one part of one day against one input. The times are missing
+/// when the input couldn't be read or parsed.
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub input: String,
+    pub result: AocResult<Answer>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+}
+
+impl Record {
+    /// One record per part: the answers if `timed` succeeded, or its error for every part.
+    pub fn from_timed(day: u32, input: &str, parts: &[Part], timed: AocResult<Timed>) -> Vec<Record> {
+        let record = |part: Part, result, parse_time, solve_time| Record {
+            day,
+            part,
+            input: input.to_string(),
+            result,
+            parse_time,
+            solve_time,
+        };
+        match timed {
+            Ok(timed) => parts
+                .iter()
+                .zip(timed.parts)
+                .map(|(part, (result, time))| record(*part, result, Some(timed.parse), Some(time)))
+                .collect(),
+            Err(e) => parts
+                .iter()
+                .map(|part| record(*part, Err(e.clone()), None, None))
+                .collect(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let (answer, error) = match &self.result {
+            Ok(answer) => (answer_to_json(answer), Value::Null),
+            Err(e) => (Value::Null, Value::from(e.to_string())),
+        };
+        json!({
+            "day": self.day,
+            "part": self.part.to_string(),
+            "input": self.input,
+            "answer": answer,
+            "error": error,
+            "parse_ns": self.parse_time.map(|t| t.as_nanos() as u64),
+            "solve_ns": self.solve_time.map(|t| t.as_nanos() as u64),
+        })
+    }
+
+    fn to_csv(&self) -> String {
+        let (answer, error) = match &self.result {
+            Ok(answer) => (answer.to_string(), String::new()),
+            Err(e) => (String::new(), e.to_string()),
+        };
+        let nanos = |t: Option<Duration>| t.map(|t| t.as_nanos().to_string()).unwrap_or_default();
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(&self.input),
+            csv_field(&answer),
+            csv_field(&error),
+            nanos(self.parse_time),
+            nanos(self.solve_time),
+        ]
+        .join(",")
+    }
+}
+
+const CSV_HEADER: &str = "day,part,input,answer,error,parse_ns,solve_ns";
+
+fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Int(n) => Value::from(*n),
+        Answer::Text(s) => Value::from(s.clone()),
+        Answer::Art(rows) => Value::from(rows.clone()),
+    }
+}
+
+/// Quote a field if it holds a comma, quote or newline, doubling any quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render records as JSON lines or CSV. Text output is left to the caller.
+pub fn render(format: Format, records: &[Record]) -> String {
+    let mut output = String::new();
+    match format {
+        Format::Text => {}
+        Format::Json => {
+            for record in records {
+                output.push_str(&record.to_json().to_string());
+                output.push('\n');
+            }
+        }
+        Format::Csv => {
+            output.push_str(CSV_HEADER);
+            output.push('\n');
+            for record in records {
+                output.push_str(&record.to_csv());
+                output.push('\n');
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 10,
+                part: Part::B,
+                input: String::from("input.txt"),
+                result: Ok(Answer::Art(vec![String::from("#."), String::from(".#")])),
+                parse_time: Some(Duration::from_micros(3)),
+                solve_time: Some(Duration::from_nanos(40)),
+            },
+            Record {
+                day: 17,
+                part: Part::B,
+                input: String::from("a,b.txt"),
+                result: Err(AocError::Unimplemented),
+                parse_time: None,
+                solve_time: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_json() {
+        let output = render(Format::Json, &records());
+        let lines: Vec<Value> = output.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines[0]["answer"], json!(["#.", ".#"]));
+        assert_eq!(lines[0]["parse_ns"], json!(3000));
+        assert_eq!(lines[1]["answer"], Value::Null);
+        assert_eq!(lines[1]["error"], json!("not implemented yet"));
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let expected = "day,part,input,answer,error,parse_ns,solve_ns
+10,b,input.txt,\"#.\n.#\",,3000,40
+17,b,\"a,b.txt\",,not implemented yet,,
+";
+        assert_eq!(render(Format::Csv, &records()), expected);
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("JSON".parse::<Format>(), Ok(Format::Json));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
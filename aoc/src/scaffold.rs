@@ -0,0 +1,210 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::{AocError, AocResult};
+
+const TEMPLATE_DIR: &str = "rust_template";
+const EXAMPLE_NAME: &str = "test_input.txt";
+
+/// Start a new day from `rust_template`: copy it to `NN/rust` as crate `dayNN`, add an empty
+/// `NN/test_input.txt`, and register the day with the workspace, the runner and
+/// `answers.toml`, and the fuzz crate if there is one. Returns every file created or changed.
+pub fn new_day(root: &Path, day: u32) -> AocResult<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::Usage(format!("Invalid day: {}", day)));
+    }
+    let day_dir = root.join(format!("{:02}", day));
+    let crate_dir = day_dir.join("rust");
+    if crate_dir.exists() {
+        return Err(AocError::Usage(format!("{} already exists", crate_dir.display())));
+    }
+
+    let mut changed = Vec::new();
+    let template = root.join(TEMPLATE_DIR);
+    for source in files_under(&template)? {
+        let relative = source.strip_prefix(&template).unwrap();
+        let contents = read(&source)?;
+        let contents = if relative == Path::new("Cargo.toml") {
+            // The day crates sit one level deeper than the template.
+            contents.replace("path = \"../", "path = \"../../")
+        } else {
+            contents
+        };
+        changed.push(write(&crate_dir.join(relative), &rename(&contents, day))?);
+    }
+
+    let example = day_dir.join(EXAMPLE_NAME);
+    if !example.exists() {
+        changed.push(write(&example, "")?);
+    }
+
+    let path = root.join("Cargo.toml");
+    let member = format!("    \"{:02}/rust\",", day);
+    let contents = insert_sorted(&read(&path)?, &member, |line| line.ends_with("/rust\","));
+    changed.push(write(&path, &contents)?);
+
+    let path = root.join("aoc").join("Cargo.toml");
+    let dependency = format!("day{0:02} = {{ path = \"../{0:02}/rust\" }}", day);
+    let contents = insert_sorted(&read(&path)?, &dependency, |line| line.starts_with("day"));
+    changed.push(write(&path, &contents)?);
+
+    let path = root.join("aoc").join("src").join("days.rs");
+    let entry = format!("        Day::new::<day{0:02}::Day{0:02}>({1}),", day, day);
+    let contents = insert_sorted(&read(&path)?, &entry, |line| {
+        line.trim_start().starts_with("Day::new::<day")
+    });
+    changed.push(write(&path, &contents)?);
+
+    let path = root.join("answers.toml");
+    let mut answers = fs::read_to_string(&path).unwrap_or_default();
+    let section = format!("[{:02}.\"{}\"]", day, EXAMPLE_NAME);
+    if !answers.contains(&section) {
+        if !answers.is_empty() && !answers.ends_with("\n\n") {
+            answers.push('\n');
+        }
+        answers.push_str(&format!("{}\n# a = ...\n# b = ...\n", section));
+        changed.push(write(&path, &answers)?);
+    }
+
+    let path = root.join("fuzz").join("Cargo.toml");
+    if path.exists() {
+        let dependency = format!("day{0:02} = {{ path = \"../{0:02}/rust\" }}", day);
+        let mut contents = insert_sorted(&read(&path)?, &dependency, |line| line.starts_with("day"));
+        contents.push_str(&format!(
+            "\n[[bin]]\nname = \"day{0:02}\"\npath = \"fuzz_targets/day{0:02}.rs\"\n\
+             test = false\ndoc = false\nbench = false\n",
+            day
+        ));
+        changed.push(write(&path, &contents)?);
+        let target = root.join("fuzz").join("fuzz_targets").join(format!("day{:02}.rs", day));
+        changed.push(write(&target, &fuzz_target(day))?);
+    }
+    Ok(changed)
+}
+
+/// A fuzz target that feeds arbitrary text to the day's parser.
+fn fuzz_target(day: u32) -> String {
+    format!(
+        "#![no_main]\n\nuse common::Solution;\nuse libfuzzer_sys::fuzz_target;\n\n\
+         // Parsing may fail, but must never panic.\n\
+         fuzz_target!(|input: &str| {{\n    let _ = day{0:02}::Day{0:02}::parse(input);\n}});\n",
+        day
+    )
+}
+
+/// Give the template's crate and solution the day's names.
+fn rename(contents: &str, day: u32) -> String {
+    contents
+        .replace("Template", &format!("Day{:02}", day))
+        .replace("template", &format!("day{:02}", day))
+}
+
+/// Insert `line` among the lines for which `is_entry` holds, keeping them in order. The
+/// entries are zero-padded by day, so they sort as text.
+fn insert_sorted<F: Fn(&str) -> bool>(text: &str, line: &str, is_entry: F) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let entries: Vec<usize> = (0..lines.len()).filter(|&i| is_entry(lines[i])).collect();
+    let position = entries
+        .iter()
+        .find(|&&i| lines[i] > line)
+        .copied()
+        .or_else(|| entries.last().map(|i| i + 1))
+        .unwrap_or(lines.len());
+    let mut result: Vec<&str> = lines[..position].to_vec();
+    result.push(line);
+    result.extend(&lines[position..]);
+    let mut result = result.join("\n");
+    result.push('\n');
+    result
+}
+
+fn read(path: &Path) -> AocResult<String> {
+    fs::read_to_string(path).map_err(|e| AocError::Io(format!("{}: {}", path.display(), e)))
+}
+
+fn write(path: &Path, contents: &str) -> AocResult<PathBuf> {
+    let io_error = |e: std::io::Error| AocError::Io(format!("{}: {}", path.display(), e));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, contents).map_err(io_error)?;
+    Ok(path.to_path_buf())
+}
+
+/// Every file in the template, skipping any build output.
+fn files_under(dir: &Path) -> AocResult<Vec<PathBuf>> {
+    let io_error = |e: std::io::Error| AocError::Io(format!("{}: {}", dir.display(), e));
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name != "target") {
+                files.extend(files_under(&path)?);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let text = "members = [\n    \"aoc\",\n    \"02/rust\",\n    \"05/rust\",\n]\n";
+        let is_entry = |line: &str| line.ends_with("/rust\",");
+        assert_eq!(
+            insert_sorted(text, "    \"03/rust\",", is_entry),
+            "members = [\n    \"aoc\",\n    \"02/rust\",\n    \"03/rust\",\n    \"05/rust\",\n]\n"
+        );
+        assert_eq!(
+            insert_sorted(text, "    \"18/rust\",", is_entry),
+            "members = [\n    \"aoc\",\n    \"02/rust\",\n    \"05/rust\",\n    \"18/rust\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let real_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for file in files_under(&real_root.join(TEMPLATE_DIR)).unwrap() {
+            let relative = file.strip_prefix(&real_root).unwrap();
+            write(&root.join(relative), &read(&file).unwrap()).unwrap();
+        }
+        write(&root.join("Cargo.toml"), "members = [\n    \"17/rust\",\n]\n").unwrap();
+        write(&root.join("aoc/Cargo.toml"), "[dependencies]\nday17 = { path = \"../17/rust\" }\n")
+            .unwrap();
+        write(
+            &root.join("aoc/src/days.rs"),
+            "    vec![\n        Day::new::<day17::Day17>(17),\n    ]\n",
+        )
+        .unwrap();
+        write(&root.join("answers.toml"), "[17.\"test_input.txt\"]\na = 3068\n").unwrap();
+        write(&root.join("fuzz/Cargo.toml"), "[dependencies]\nday17 = { path = \"../17/rust\" }\n")
+            .unwrap();
+
+        new_day(&root, 18).unwrap();
+        let contents = |path: &str| read(&root.join(path)).unwrap();
+        assert!(contents("18/rust/Cargo.toml").contains("name = \"day18\""));
+        assert!(contents("18/rust/Cargo.toml").contains("path = \"../../common\""));
+        assert!(contents("18/rust/src/lib.rs").contains("pub struct Day18;"));
+        assert!(contents("18/rust/src/main.rs").contains("use day18::Day18;"));
+        assert_eq!(contents("18/test_input.txt"), "");
+        assert!(contents("Cargo.toml").contains("\"17/rust\",\n    \"18/rust\","));
+        assert!(contents("aoc/Cargo.toml").ends_with("day18 = { path = \"../18/rust\" }\n"));
+        assert!(contents("aoc/src/days.rs").contains("Day::new::<day18::Day18>(18),\n    ]"));
+        assert!(contents("fuzz/Cargo.toml").contains("day18 = { path = \"../18/rust\" }\n\n[[bin]]"));
+        let day17_target = read(&real_root.join("fuzz/fuzz_targets/day17.rs")).unwrap();
+        assert_eq!(contents("fuzz/fuzz_targets/day18.rs"), day17_target.replace("17", "18"));
+        assert!(contents("answers.toml").ends_with("\n\n[18.\"test_input.txt\"]\n# a = ...\n# b = ...\n"));
+
+        assert!(new_day(&root, 18).is_err());
+        assert!(new_day(&root, 26).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,293 @@
+use std::path::{Path, PathBuf};
+
+use common::{AocError, AocResult, Answer, Part};
+
+use crate::answers::Answers;
+use crate::days;
+use crate::inputs::{InputStore, Kind};
+use crate::languages::{self, Language};
+
+/// The inputs a part is run against. Only the real input's recorded answers can verify a
+/// part: passing the example doesn't mean the real answer is right, so examples are run
+/// with no answer to check against. They're still run because an unimplemented part says
+/// so on any input, so without the real input the example is how `stars` finds parts with
+/// no solution.
+const CHECKED_INPUTS: [Kind; 2] = [Kind::Input, Kind::Example];
+const PARTS: [Part; 2] = [Part::A, Part::B];
+const DAYS: u32 = 25;
+
+/// Each language's column is as wide as its name, but at least wide enough for the marks.
+fn column_width(language: Language) -> usize {
+    language.name().len().max(4)
+}
+
+/// What we found out about one part in one language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Matches every recorded answer it was checked against.
+    Verified,
+    /// Runs, but fails or gets a recorded answer wrong.
+    Wrong,
+    /// There's a solution, but nothing recorded to check it against here.
+    Unchecked,
+    /// There's no solution.
+    Missing,
+    /// The solution couldn't be run here (no inputs, or no toolchain).
+    Unknown,
+}
+
+/// The README's Day × Language table. Each cell holds a mark per part: `*` for a verified
+/// part, `/` for one that's written but wrong or unverified, and a space for no solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarTable {
+    cells: Vec<[[char; 2]; 4]>,
+}
+
+impl StarTable {
+    /// Find the table in the README, returning it with the range of lines it occupies.
+    pub fn parse(readme: &str) -> Option<(StarTable, std::ops::Range<usize>)> {
+        let lines: Vec<&str> = readme.lines().collect();
+        let start = lines.iter().position(|line| line.starts_with("| Day | Python |"))?;
+        let mut cells = vec![[[' '; 2]; 4]; DAYS as usize];
+        let mut end = start + 2;
+        while let Some(row) = lines.get(end).filter(|line| line.starts_with('|')) {
+            let fields: Vec<&str> = row.split('|').collect();
+            let day = fields.get(1)?.trim().parse::<usize>().ok()?;
+            if (1..=DAYS as usize).contains(&day) {
+                for (i, field) in fields[2..].iter().take(4).enumerate() {
+                    // Right-aligned between one space of padding either side.
+                    let content: Vec<char> = field.chars().skip(1).collect();
+                    let content = &content[..content.len().saturating_sub(1)];
+                    let marks = &content[content.len().saturating_sub(2)..];
+                    for (j, mark) in marks.iter().rev().enumerate() {
+                        cells[day - 1][i][1 - j] = *mark;
+                    }
+                }
+            }
+            end += 1;
+        }
+        Some((StarTable { cells }, start..end))
+    }
+
+    /// Record what was found for a part. Parts that couldn't be checked keep the mark set by
+    /// hand, since it may have been verified somewhere with the real input.
+    pub fn update(&mut self, day: u32, language: Language, part: Part, status: Status) {
+        let cell = &mut self.cells[day as usize - 1][language as usize][part as usize];
+        *cell = match status {
+            Status::Verified => '*',
+            Status::Wrong => '/',
+            Status::Missing => ' ',
+            Status::Unchecked | Status::Unknown => *cell,
+        };
+    }
+
+    pub fn render(&self) -> String {
+        let mut header = String::from("| Day |");
+        let mut rule = String::from("| --- |");
+        for language in Language::ALL {
+            let width = column_width(language);
+            header.push_str(&format!(" {:>width$} |", language.name()));
+            rule.push_str(&format!(" {} |", "-".repeat(width)));
+        }
+        let mut table = format!("{}\n{}\n", header, rule);
+        for (day, row) in self.cells.iter().enumerate() {
+            table.push_str(&format!("| {:>3} |", day + 1));
+            for (language, marks) in Language::ALL.iter().zip(row) {
+                let marks: String = marks.iter().collect();
+                table.push_str(&format!(" {:>width$} |", marks, width = column_width(*language)));
+            }
+            table.push('\n');
+        }
+        table
+    }
+}
+
+/// Check every day and part in `language`, updating the table.
+pub fn check(
+    table: &mut StarTable,
+    language: Language,
+    answers: &Answers,
+    store: &InputStore,
+) -> AocResult<()> {
+    for day in 1..=DAYS {
+        // The inputs we have and their recorded answers, for each part.
+        let inputs: Vec<(PathBuf, [Option<&Answer>; 2])> = CHECKED_INPUTS
+            .iter()
+            .filter_map(|kind| {
+                let path = store.find(day, kind.file_name())?;
+                let recorded = PARTS.map(|part| match kind {
+                    Kind::Input => answers.get(day, kind.file_name(), part),
+                    Kind::Example => None,
+                });
+                Some((path, recorded))
+            })
+            .collect();
+        let statuses = match language {
+            Language::Rust => check_rust(store, day, &inputs)?,
+            _ => check_external(store, language, day, &inputs)?,
+        };
+        for (part, status) in PARTS.into_iter().zip(statuses) {
+            table.update(day, language, part, status);
+        }
+    }
+    Ok(())
+}
+
+fn check_rust(
+    store: &InputStore,
+    day: u32,
+    inputs: &[(PathBuf, [Option<&Answer>; 2])],
+) -> AocResult<[Status; 2]> {
+    let Some(solver) = days::find(day) else {
+        // A crate that isn't in the runner yet can't be checked from here.
+        let exists = store.repo_root.join(format!("{:02}", day)).join("rust").is_dir();
+        return Ok([if exists { Status::Unknown } else { Status::Missing }; 2]);
+    };
+    let mut results: [Vec<(AocResult<Answer>, Option<&Answer>)>; 2] = [vec![], vec![]];
+    for (path, recorded) in inputs {
+        let contents = std::fs::read_to_string(path)?;
+        match (solver.solve)(&contents, &PARTS) {
+            Ok(answers) => {
+                for (i, answer) in answers.into_iter().enumerate() {
+                    results[i].push((answer, recorded[i]));
+                }
+            }
+            Err(e) => {
+                for (i, result) in results.iter_mut().enumerate() {
+                    result.push((Err(e.clone()), recorded[i]));
+                }
+            }
+        }
+    }
+    Ok(results.map(|results| status_of(&results)))
+}
+
+/// Run another language's solution for each part against the inputs with a recorded
+/// answer. The solutions print their answer last, sometimes after a `Result: ` label. A part
+/// that times out on any input is left unchecked.
+fn check_external(
+    store: &InputStore,
+    language: Language,
+    day: u32,
+    inputs: &[(PathBuf, [Option<&Answer>; 2])],
+) -> AocResult<[Status; 2]> {
+    let mut statuses = [Status::Missing; 2];
+    for (i, part) in PARTS.into_iter().enumerate() {
+        let command = |input: &Path| languages::external_command(&store.repo_root, language, day, part, input);
+        if command(Path::new("")).is_none() {
+            continue;
+        }
+        let mut results = Vec::new();
+        let mut timed_out = false;
+        for (path, recorded) in inputs {
+            let Some(expected) = recorded[i] else {
+                continue;
+            };
+            let path = std::fs::canonicalize(path)?;
+            let output = match languages::output_with_timeout(&mut command(&path).unwrap(), languages::TIMEOUT) {
+                Ok(Some(output)) => output,
+                Ok(None) => {
+                    // It may just be slow, so it's neither right nor wrong.
+                    let limit = languages::TIMEOUT;
+                    log::warn!("{} day {} part {} timed out after {:?}", language.name(), day, part, limit);
+                    timed_out = true;
+                    continue;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    log::warn!("Can't run {} here: {}", language.name(), e);
+                    return Ok([Status::Unknown; 2]);
+                }
+                Err(e) => return Err(AocError::from(e)),
+            };
+            let result = if output.status.success() && languages::output_matches(&output.stdout, expected) {
+                Ok(expected.clone())
+            } else {
+                Err(AocError::Solve(String::from_utf8_lossy(&output.stdout).into_owned()))
+            };
+            results.push((result, Some(expected)));
+        }
+        statuses[i] = if timed_out { Status::Unknown } else { status_of(&results) };
+    }
+    Ok(statuses)
+}
+
+/// Sum up a part's results against each input, with the answer recorded for that input.
+fn status_of(results: &[(AocResult<Answer>, Option<&Answer>)]) -> Status {
+    if results.is_empty() {
+        return Status::Unknown;
+    }
+    if results.iter().any(|(result, _)| result == &Err(AocError::Unimplemented)) {
+        return Status::Missing;
+    }
+    // Failures only count against inputs with a recorded answer: some solutions hardcode
+    // details of the real input, so they can't solve the example.
+    let checked: Vec<bool> = results
+        .iter()
+        .filter_map(|(result, recorded)| Some(result.as_ref().ok() == Some((*recorded)?)))
+        .collect();
+    if checked.contains(&false) {
+        Status::Wrong
+    } else if checked.is_empty() {
+        Status::Unchecked
+    } else {
+        Status::Verified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "# Title
+
+| Day | Python |   Go | Rust | Scala |
+| --- | ------ | ---- | ---- | ----- |
+|   1 |     ** |   ** |   ** |    ** |
+|   2 |      * |      |   */ |       |
+|   3 |        |      |      |       |
+
+More text.
+";
+
+    #[test]
+    fn test_parse_and_render() {
+        let (table, lines) = StarTable::parse(README).unwrap();
+        assert_eq!(lines, 2..7);
+        assert_eq!(table.cells[1], [[' ', '*'], [' ', ' '], ['*', '/'], [' ', ' ']]);
+        let rendered = table.render();
+        let expected: Vec<&str> = README.lines().skip(2).take(5).collect();
+        assert_eq!(rendered.lines().take(5).collect::<Vec<_>>(), expected);
+        assert_eq!(rendered.lines().count(), 2 + 25);
+    }
+
+    #[test]
+    fn test_update() {
+        let (mut table, _) = StarTable::parse(README).unwrap();
+        table.update(2, Language::Rust, Part::B, Status::Verified);
+        table.update(1, Language::Rust, Part::B, Status::Missing);
+        table.update(1, Language::Go, Part::A, Status::Unknown);
+        table.update(1, Language::Python, Part::A, Status::Unchecked);
+        table.update(3, Language::Scala, Part::A, Status::Unchecked);
+        table.update(3, Language::Scala, Part::B, Status::Wrong);
+        assert_eq!(table.cells[0], [['*', '*'], ['*', '*'], ['*', ' '], ['*', '*']]);
+        assert_eq!(table.cells[1][2], ['*', '*']);
+        // A solution that can't be checked doesn't earn a mark.
+        assert_eq!(table.cells[2][3], [' ', '/']);
+    }
+
+    #[test]
+    fn test_status_of() {
+        let answer = Answer::from(7);
+        let wrong = Answer::from(8);
+        assert_eq!(status_of(&[]), Status::Unknown);
+        assert_eq!(status_of(&[(Ok(answer.clone()), Some(&answer))]), Status::Verified);
+        assert_eq!(
+            status_of(&[(Ok(answer.clone()), Some(&answer)), (Ok(answer.clone()), Some(&wrong))]),
+            Status::Wrong
+        );
+        assert_eq!(status_of(&[(Ok(answer.clone()), None)]), Status::Unchecked);
+        assert_eq!(status_of(&[(Err(AocError::NoAnswer), Some(&answer))]), Status::Wrong);
+        assert_eq!(status_of(&[(Err(AocError::NoAnswer), None)]), Status::Unchecked);
+        assert_eq!(status_of(&[(Err(AocError::Unimplemented), None)]), Status::Missing);
+    }
+}
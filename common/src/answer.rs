@@ -0,0 +1,99 @@
+use std::fmt;
+
+use crate::{AocError, AocResult};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// Multi-line ASCII art, one string per row (e.g. the CRT output on day 10).
+    Art(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Art(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Art(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+/// Answers are stored as `i64`, so a `u64` past `i64::MAX` is an error rather than
+/// wrapping round to a negative number.
+impl TryFrom<u64> for Answer {
+    type Error = AocError;
+
+    fn try_from(n: u64) -> AocResult<Self> {
+        i64::try_from(n)
+            .map(Answer::Int)
+            .map_err(|_| AocError::Solve(format!("{} is too big for an answer", n)))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(42).to_string(), "42");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        let art = Answer::Art(vec![String::from("#."), String::from(".#")]);
+        assert_eq!(art.to_string(), "#.\n.#");
+    }
+
+    #[test]
+    fn test_structural_equality() {
+        assert_eq!(Answer::from(7usize), Answer::Int(7));
+        assert_ne!(Answer::from(7), Answer::from("7"));
+    }
+
+    #[test]
+    fn test_from_u64() {
+        assert_eq!(Answer::try_from(7u64), Ok(Answer::Int(7)));
+        assert_eq!(Answer::try_from(i64::MAX as u64), Ok(Answer::Int(i64::MAX)));
+        assert_eq!(
+            Answer::try_from(9223372036854775808u64),
+            Err(AocError::solve("9223372036854775808 is too big for an answer"))
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Time and memory budgets for a solver run. The runner runs each solver on a thread of its
+//! own under `with_budget`, and cancels its token when the time is up; long-running loops
+//! call `checkpoint`, which turns a cancelled token or too much memory into an error so the
+//! solver unwinds on its own.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::{Cell, RefCell};
+use std::sync::{Arc, OnceLock};
+
+use crate::{AocError, AocResult};
+
+/// Shared between the runner and a solver's thread: once cancelled, every checkpoint on
+/// that thread fails with the reason given.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    reason: Arc<OnceLock<AocError>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Cancel the run. Only the first reason given sticks.
+    pub fn cancel(&self, reason: AocError) {
+        let _ = self.reason.set(reason);
+    }
+
+    pub fn reason(&self) -> Option<&AocError> {
+        self.reason.get()
+    }
+}
+
+/// The token and memory limit for the solver running on this thread.
+struct Limits {
+    token: CancellationToken,
+    memory: Option<usize>,
+    // What this thread had allocated before the run started.
+    baseline: isize,
+}
+
+thread_local! {
+    static LIMITS: RefCell<Option<Limits>> = const { RefCell::new(None) };
+    // Bytes allocated and not yet freed by this thread, kept by `CountingAllocator`.
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Run `solve` on this thread under `token`, failing its checkpoints if it holds on to more
+/// than `memory` bytes. Memory is only counted where `CountingAllocator` is installed.
+pub fn with_budget<R>(token: CancellationToken, memory: Option<usize>, solve: impl FnOnce() -> R) -> R {
+    let limits = Limits { token, memory, baseline: allocated() };
+    let previous = LIMITS.with(|current| current.replace(Some(limits)));
+    let result = solve();
+    LIMITS.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// Called by long-running loops: fails once the run has been cancelled or has gone over its
+/// memory budget, and does nothing outside `with_budget`.
+pub fn checkpoint() -> AocResult<()> {
+    LIMITS.with(|current| {
+        let Some(limits) = &*current.borrow() else {
+            return Ok(());
+        };
+        if let Some(reason) = limits.token.reason() {
+            return Err(reason.clone());
+        }
+        match limits.memory {
+            Some(memory) if allocated() - limits.baseline > memory as isize => {
+                let error = AocError::OverBudget(memory);
+                limits.token.cancel(error.clone());
+                Err(error)
+            }
+            _ => Ok(()),
+        }
+    })
+}
+
+/// Bytes this thread has allocated and not yet freed.
+pub fn allocated() -> isize {
+    ALLOCATED.try_with(Cell::get).unwrap_or(0)
+}
+
+fn count(bytes: isize) {
+    let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + bytes));
+}
+
+/// The system allocator, counting each thread's allocations for the memory budget. Memory
+/// freed on a different thread from the one that allocated it is counted against the thread
+/// that freed it, which is close enough for a budget.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            count(layout.size() as isize);
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            count(layout.size() as isize);
+        }
+        pointer
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            count(new_size as isize - layout.size() as isize);
+        }
+        new_pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        count(-(layout.size() as isize));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_fails_once_cancelled() {
+        assert_eq!(checkpoint(), Ok(()));
+        let token = CancellationToken::new();
+        let result = with_budget(token.clone(), None, || {
+            checkpoint()?;
+            token.cancel(AocError::TimedOut(std::time::Duration::from_secs(1)));
+            token.cancel(AocError::NoAnswer);
+            checkpoint()
+        });
+        assert_eq!(result, Err(AocError::TimedOut(std::time::Duration::from_secs(1))));
+        // The limits only apply inside the run.
+        assert_eq!(checkpoint(), Ok(()));
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+use std::num::ParseIntError;
+
+pub type AocResult<T> = Result<T, AocError>;
+
+/// Everything that can go wrong between reading an input and printing an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    /// The input couldn't be read.
+    Io(String),
+    /// The input couldn't be parsed. `line` and `column` are 1-based, with 0 meaning
+    /// "not known yet"; `text` is the offending piece of input.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// The input parsed, but the solver couldn't make sense of it.
+    Solve(String),
+    /// The solver ran to completion without finding an answer.
+    NoAnswer,
+    /// This part hasn't been solved yet.
+    Unimplemented,
+    /// The solver was still running when its time ran out.
+    TimedOut(std::time::Duration),
+    /// The solver held on to more memory than it was allowed, in bytes.
+    OverBudget(usize),
+    /// The command line didn't make sense.
+    Usage(String),
+}
+
+impl AocError {
+    /// A parse error with no position yet; see `on_line` and `at`.
+    pub fn parse(text: &str, message: &str) -> Self {
+        AocError::Parse {
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn solve(message: &str) -> Self {
+        AocError::Solve(message.to_string())
+    }
+
+    /// Set the position of a parse error. Other errors are returned unchanged.
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        if let AocError::Parse {
+            line: l, column: c, ..
+        } = &mut self
+        {
+            *l = line;
+            *c = column;
+        }
+        self
+    }
+
+    /// Fill in the line number of a parse error raised while parsing `line_text`. If the
+    /// error didn't say which text was at fault, blame the whole line; if it didn't say
+    /// which column, use the first place the offending text appears. Positions that are
+    /// already known are kept, so nested parsers can call this on the way out.
+    pub fn on_line(mut self, line: usize, line_text: &str) -> Self {
+        if let AocError::Parse {
+            line: l,
+            column: c,
+            text,
+            ..
+        } = &mut self
+        {
+            if *l != 0 {
+                return self;
+            }
+            *l = line;
+            if text.is_empty() {
+                *text = line_text.to_string();
+            }
+            if *c == 0 {
+                *c = line_text.find(text.as_str()).map_or(0, |i| i + 1);
+            }
+        }
+        self
+    }
+
+    /// Shift the line number of a parse error by `offset`, for errors raised while
+    /// parsing a block that starts partway through the input.
+    pub fn offset_lines(mut self, offset: usize) -> Self {
+        if let AocError::Parse { line, .. } = &mut self {
+            if *line != 0 {
+                *line += offset;
+            }
+        }
+        self
+    }
+
+    /// The process exit code for this error: 2 for bad usage, 1 for everything else.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            AocError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(message) => write!(f, "I/O error: {}", message),
+            AocError::Parse {
+                line,
+                column,
+                text,
+                message,
+            } => {
+                write!(f, "parse error")?;
+                if *line != 0 {
+                    write!(f, " at line {}", line)?;
+                    if *column != 0 {
+                        write!(f, ", column {}", column)?;
+                    }
+                }
+                write!(f, ": {}", message)?;
+                if !text.is_empty() {
+                    write!(f, " (found {:?})", text)?;
+                }
+                Ok(())
+            }
+            AocError::Solve(message) => write!(f, "{}", message),
+            AocError::NoAnswer => write!(f, "no answer found"),
+            AocError::Unimplemented => write!(f, "not implemented yet"),
+            AocError::TimedOut(limit) => write!(f, "timed out after {:?}", limit),
+            AocError::OverBudget(limit) => {
+                write!(f, "over budget: used more than {} MiB", limit / (1024 * 1024))
+            }
+            AocError::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<std::io::Error> for AocError {
+    fn from(e: std::io::Error) -> Self {
+        AocError::Io(e.to_string())
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> Self {
+        AocError::parse("", &e.to_string())
+    }
+}
+
+/// Parse each line of `contents` with `parse_line`, tagging any parse error with the
+/// line it came from.
+pub fn parse_lines<T, F>(contents: &str, mut parse_line: F) -> AocResult<Vec<T>>
+where
+    F: FnMut(&str) -> AocResult<T>,
+{
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.on_line(i + 1, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines_finds_position() {
+        let result = parse_lines("1-2\n3-x", |line| {
+            let (a, b) = line.split_once('-').ok_or(AocError::parse("", "no dash"))?;
+            if b == "x" {
+                return Err(AocError::parse(b, "not a number"));
+            }
+            Ok((a.to_string(), b.to_string()))
+        });
+        let error = result.unwrap_err();
+        assert_eq!(error, AocError::parse("x", "not a number").at(2, 3));
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 2, column 3: not a number (found \"x\")"
+        );
+    }
+
+    #[test]
+    fn test_on_line_keeps_known_position() {
+        let error = AocError::parse("b", "bad").at(4, 2).on_line(1, "abc");
+        assert_eq!(error, AocError::parse("b", "bad").at(4, 2));
+        let error = AocError::from("z".parse::<i32>().unwrap_err()).on_line(3, "z");
+        assert_eq!(error, AocError::parse("z", "invalid digit found in string").at(3, 1));
+    }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(AocError::Usage(String::from("usage")).exit_code(), 2);
+        assert_eq!(AocError::NoAnswer.exit_code(), 1);
+    }
+}
@@ -0,0 +1,263 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Anything that can be searched: a set of nodes, each with edges to its neighbours.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes reachable in one step from `node`.
+    fn neighbors(&self, node: &Self::Node) -> Vec<Self::Node>;
+
+    /// The cost of the step from `from` to its neighbour `to`. Unweighted graphs keep the
+    /// default of 1, which makes Dijkstra and BFS agree.
+    fn cost(&self, _from: &Self::Node, _to: &Self::Node) -> usize {
+        1
+    }
+}
+
+/// A route found by a search: every node from the start to the goal inclusive, and the
+/// total cost of the steps between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: usize,
+}
+
+impl<N> Path<N> {
+    /// How many steps the path takes, which is one fewer than the nodes on it.
+    pub fn steps(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn end(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+}
+
+/// Walk back through `parents` from `end` to a node with no parent (a start).
+fn reconstruct<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, end: &N) -> Vec<N> {
+    let mut nodes = vec![end.clone()];
+    while let Some(parent) = parents.get(&nodes[nodes.len() - 1]) {
+        nodes.push(parent.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+/// Breadth-first search from any of `starts` to the nearest node satisfying `is_goal`,
+/// counting every step as 1 whatever `cost` says.
+pub fn bfs<G, I, F>(graph: &G, starts: I, is_goal: F) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+    F: Fn(&G::Node) -> bool,
+{
+    let mut parents: HashMap<G::Node, G::Node> = HashMap::new();
+    let mut distances: HashMap<G::Node, usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if distances.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        if is_goal(&node) {
+            let nodes = reconstruct(&parents, &node);
+            return Some(Path { nodes, cost: distance });
+        }
+        for next in graph.neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// The number of steps from `start` to every node reachable from it, including `start`
+/// itself at 0.
+pub fn bfs_distances<G: Graph>(graph: &G, start: &G::Node) -> HashMap<G::Node, usize> {
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start.clone()]);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in graph.neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// The cheapest path from any of `starts` to a node satisfying `is_goal`, by edge cost.
+pub fn dijkstra<G, I, F>(graph: &G, starts: I, is_goal: F) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+    F: Fn(&G::Node) -> bool,
+{
+    astar(graph, starts, is_goal, |_| 0)
+}
+
+/// A* search: Dijkstra guided by `heuristic`, an estimate of the remaining cost from a
+/// node to the goal. The path is only guaranteed to be the cheapest if the heuristic never
+/// overestimates, e.g. the Manhattan distance on a grid.
+pub fn astar<G, I, F, H>(graph: &G, starts: I, is_goal: F, heuristic: H) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+    F: Fn(&G::Node) -> bool,
+    H: Fn(&G::Node) -> usize,
+{
+    let mut parents: HashMap<G::Node, G::Node> = HashMap::new();
+    let mut costs: HashMap<G::Node, usize> = HashMap::new();
+    // The heap holds indices into `nodes`, so nodes needn't be `Ord` themselves.
+    let mut nodes: Vec<G::Node> = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if costs.insert(start.clone(), 0).is_none() {
+            heap.push(Reverse((heuristic(&start), 0, nodes.len())));
+            nodes.push(start);
+        }
+    }
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        // A cheaper route to this node was found after this entry was queued.
+        if cost > costs[&node] {
+            continue;
+        }
+        if is_goal(&node) {
+            let nodes = reconstruct(&parents, &node);
+            return Some(Path { nodes, cost });
+        }
+        for next in graph.neighbors(&node) {
+            let next_cost = cost + graph.cost(&node, &next);
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, nodes.len())));
+                nodes.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// Floyd–Warshall: the cheapest cost between every pair of `nodes`, as
+/// `distances[from][to]`. Pairs with no route between them are left out; every node is
+/// at 0 from itself. Only edges between the given nodes are followed.
+pub fn floyd_warshall<G: Graph>(
+    graph: &G,
+    nodes: &[G::Node],
+) -> HashMap<G::Node, HashMap<G::Node, usize>> {
+    let n = nodes.len();
+    let index: HashMap<&G::Node, usize> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    let mut dist = vec![vec![None; n]; n];
+    for (i, node) in nodes.iter().enumerate() {
+        dist[i][i] = Some(0);
+        for next in graph.neighbors(node) {
+            if let Some(&j) = index.get(&next) {
+                let cost = graph.cost(node, &next);
+                if dist[i][j].is_none_or(|d| cost < d) {
+                    dist[i][j] = Some(cost);
+                }
+            }
+        }
+    }
+    for k in 0..n {
+        // Going via k can't improve the routes from k itself, so its row is fixed this round.
+        let from_k = dist[k].clone();
+        for row in dist.iter_mut() {
+            let Some(ik) = row[k] else { continue };
+            for (d, kj) in row.iter_mut().zip(&from_k) {
+                if let Some(kj) = kj {
+                    if d.is_none_or(|d| ik + kj < d) {
+                        *d = Some(ik + kj);
+                    }
+                }
+            }
+        }
+    }
+    nodes
+        .iter()
+        .zip(dist)
+        .map(|(from, row)| {
+            let reachable = nodes
+                .iter()
+                .zip(row)
+                .filter_map(|(to, d)| d.map(|d| (to.clone(), d)))
+                .collect();
+            (from.clone(), reachable)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted, directed graph:
+    /// a -1-> b -1-> c -1-> d, with a shortcut a -5-> d and a dead end e.
+    struct Weighted;
+
+    impl Graph for Weighted {
+        type Node = char;
+
+        fn neighbors(&self, node: &char) -> Vec<char> {
+            match node {
+                'a' => vec!['d', 'b'],
+                'b' => vec!['c'],
+                'c' => vec!['d'],
+                _ => vec![],
+            }
+        }
+
+        fn cost(&self, from: &char, to: &char) -> usize {
+            if (*from, *to) == ('a', 'd') {
+                5
+            } else {
+                1
+            }
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(&Weighted, ['a'], |n| *n == 'd').unwrap();
+        assert_eq!(path.nodes, vec!['a', 'd']);
+        assert_eq!(path.steps(), 1);
+        assert_eq!(bfs(&Weighted, ['c', 'a'], |n| *n == 'd').unwrap().start(), &'c');
+        assert_eq!(bfs(&Weighted, ['a'], |n| *n == 'e'), None);
+        assert_eq!(bfs_distances(&Weighted, &'b'), HashMap::from([('b', 0), ('c', 1), ('d', 2)]));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let path = dijkstra(&Weighted, ['a'], |n| *n == 'd').unwrap();
+        assert_eq!(path.nodes, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.end(), &'d');
+        let estimate = |n: &char| (b'd' - *n as u8) as usize;
+        assert_eq!(astar(&Weighted, ['a'], |n| *n == 'd', estimate), Some(path));
+        assert_eq!(dijkstra(&Weighted, ['d'], |n| *n == 'a'), None);
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let distances = floyd_warshall(&Weighted, &['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(distances[&'a'][&'d'], 3);
+        assert_eq!(distances[&'b'][&'b'], 0);
+        assert_eq!(distances[&'d'].len(), 1);
+        assert!(!distances[&'a'].contains_key(&'e'));
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{AocError, AocResult};
+use crate::point::Point;
+
+/// A dense, rectangular grid, stored row by row, with `(0, 0)` at the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parse a character map such as a height map, with `cell` turning each character into
+    /// a cell or rejecting it (reported as `what`, e.g. "Invalid tree height"). The map
+    /// must be a non-empty rectangle.
+    pub fn parse<F>(text: &str, what: &str, cell: F) -> AocResult<Grid<T>>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for (y, line) in text.lines().enumerate() {
+            let row_start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(c)
+                    .ok_or_else(|| AocError::parse(&c.to_string(), what).at(y + 1, x + 1))?;
+                cells.push(value);
+            }
+            let row_width = cells.len() - row_start;
+            if y == 0 {
+                width = row_width;
+            } else if row_width != width {
+                let error = AocError::parse("", "Rows must all be the same length");
+                return Err(error.on_line(y + 1, line));
+            }
+            height += 1;
+        }
+        if width == 0 {
+            return Err(AocError::parse("", "Empty grid").at(1, 1));
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    fn index_of(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new((i % width) as i32, (i / width) as i32))
+    }
+
+    /// Every point with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The first point, row by row, whose cell matches.
+    pub fn find<F>(&self, f: F) -> Option<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.iter().find(|(_, cell)| f(cell)).map(|(p, _)| p)
+    }
+
+    /// The orthogonally adjacent points that are inside the grid.
+    pub fn neighbors_4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbors_4().filter(|n| self.contains(*n))
+    }
+
+    /// The adjacent points, including diagonals, that are inside the grid.
+    pub fn neighbors_8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbors_8().filter(|n| self.contains(*n))
+    }
+
+    /// The points from `p` (exclusive) to the edge of the grid, stepping by `direction`.
+    pub fn walk(&self, p: Point, direction: Point) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(p + direction), move |q| Some(*q + direction))
+            .take_while(|q| self.contains(*q))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draw the grid with one character per cell.
+    pub fn render<F>(&self, f: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            output.extend(self.row(y).iter().map(&f));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", p, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", p, width, height))
+    }
+}
+
+/// Each row on its own line, with each cell's `Display` run together.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A grid that only stores the cells that are set, for maps that are large, unbounded or
+/// mostly empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Parse a character map, keeping only the characters that `cell` turns into a cell.
+    pub fn parse<F>(text: &str, cell: F) -> SparseGrid<T>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut grid = SparseGrid::new();
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = cell(c) {
+                    grid.insert(Point::new(x as i32, y as i32), value);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, value)| (*p, value))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The top-left and bottom-right corners of the smallest rectangle holding every cell.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.points();
+        let first = points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Draw the cells within `bounds`, with `f` choosing the character for each point given
+    /// its cell, if any.
+    pub fn render<F>(&self, bounds: (Point, Point), f: F) -> String
+    where
+        F: Fn(Point, Option<&T>) -> char,
+    {
+        let (min, max) = bounds;
+        let mut output = String::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let p = Point::new(x, y);
+                output.push(f(p, self.get(p)));
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> SparseGrid<T> {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter);
+    }
+}
+
+/// The cells within the bounds, with `.` wherever nothing is set.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.get(Point::new(x, y)) {
+                    Some(cell) => write!(f, "{}", cell)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    fn digits(text: &str) -> AocResult<Grid<u32>> {
+        Grid::parse(text, "Invalid digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn test_parse_and_get() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&6));
+        assert_eq!(grid[Point::new(0, 1)], 4);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.find(|&d| d == 5), Some(Point::new(1, 1)));
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            digits("123\n4x6").err(),
+            Some(AocError::parse("x", "Invalid digit").at(2, 2))
+        );
+        assert_eq!(
+            digits("123\n45").err(),
+            Some(AocError::parse("45", "Rows must all be the same length").at(2, 1))
+        );
+        assert_eq!(digits("").err(), Some(AocError::parse("", "Empty grid").at(1, 1)));
+    }
+
+    #[test]
+    fn test_neighbors_and_walk() {
+        let mut grid = Grid::new(3, 3, '.');
+        let corner: Vec<Point> = grid.neighbors_4(Point::ORIGIN).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors_8(Point::new(1, 1)).count(), 8);
+        let walked: Vec<Point> = grid.walk(Point::new(1, 2), Point::UP).collect();
+        assert_eq!(walked, vec![Point::new(1, 1), Point::new(1, 0)]);
+
+        grid[Point::new(1, 1)] = '#';
+        assert_eq!(grid.render(|c| *c), "...\n.#.\n...\n");
+        assert_eq!(grid.map(|c| *c == '#').iter().filter(|(_, b)| **b).count(), 1);
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::parse("#..\n..#", |c| (c == '#').then_some('#'));
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(Point::new(2, 1)));
+        grid.insert(Point::new(-1, 3), 'o');
+        assert_eq!(grid.bounds(), Some((Point::new(-1, 0), Point::new(2, 3))));
+        assert_eq!(grid.to_string(), ".#..\n...#\n....\no...\n");
+        let rendered = grid.render((Point::ORIGIN, Point::new(1, 0)), |p, cell| match cell {
+            Some(c) => *c,
+            None if p.x == 1 => '+',
+            None => '.',
+        });
+        assert_eq!(rendered, "#+\n");
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+    }
+
+    /// Rectangular maps of digits, as text.
+    fn digit_map() -> impl Strategy<Value = String> {
+        (1..8usize, 1..8usize)
+            .prop_flat_map(|(width, height)| vec(vec(0..10u32, width), height))
+            .prop_map(|rows| {
+                let rows: Vec<String> = rows
+                    .iter()
+                    .map(|row| row.iter().map(|d| d.to_string()).collect())
+                    .collect();
+                rows.join("\n")
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_display_round_trips(text in digit_map()) {
+            let grid = Grid::parse(&text, "Invalid digit", |c| c.to_digit(10)).unwrap();
+            prop_assert_eq!(grid.to_string(), format!("{}\n", text));
+            let reparsed = Grid::parse(&grid.to_string(), "Invalid digit", |c| c.to_digit(10));
+            prop_assert_eq!(reparsed, Ok(grid));
+        }
+
+        #[test]
+        fn prop_parse_never_panics(text in "[0-9.\n]{0,40}") {
+            let _ = Grid::parse(&text, "Invalid digit", |c| c.to_digit(10));
+            let _ = SparseGrid::parse(&text, |c| c.to_digit(10)).to_string();
+        }
+    }
+}
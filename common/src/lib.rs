@@ -0,0 +1,21 @@
+mod answer;
+pub mod budget;
+mod error;
+mod generator;
+pub mod graph;
+mod grid;
+mod logging;
+mod point;
+mod solution;
+
+pub use answer::Answer;
+pub use error::{parse_lines, AocError, AocResult};
+pub use generator::{Generator, Rng};
+pub use graph::Graph;
+pub use grid::{Grid, SparseGrid};
+pub use logging::{init_logging, take_verbosity};
+pub use point::Point;
+pub use solution::{
+    exit, parse_parts, parse_run_args, parse_run_args_without_input, print_answer, read_input,
+    run, run_batch, Part, RunArgs, Solution,
+};
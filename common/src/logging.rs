@@ -0,0 +1,50 @@
+use log::LevelFilter;
+
+/// Remove `-v`, `-vv` and so on (or a repeated `--verbose`) from the arguments, returning
+/// how many levels of verbosity were asked for.
+pub fn take_verbosity(args: &mut Vec<String>) -> u8 {
+    let mut verbosity = 0;
+    args.retain(|arg| {
+        let count = match arg.as_str() {
+            "--verbose" => 1,
+            flag if flag.len() > 1 && flag.starts_with('-') && flag[1..].chars().all(|c| c == 'v') => {
+                flag.len() - 1
+            }
+            _ => return true,
+        };
+        verbosity += count as u8;
+        false
+    });
+    verbosity
+}
+
+/// Send the solvers' logging to stderr. By default only warnings are shown; `-v` shows
+/// debug messages and `-vv` step-by-step traces. `RUST_LOG` can then override that per
+/// module, e.g. `RUST_LOG=day17::chamber=trace`.
+pub fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    let _ = env_logger::Builder::new()
+        .filter_level(level)
+        .parse_env("RUST_LOG")
+        .format_timestamp(None)
+        .try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_verbosity() {
+        let mut args: Vec<String> = ["aoc", "-vv", "14", "-", "--verbose", "a"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(take_verbosity(&mut args), 3);
+        assert_eq!(args, vec!["aoc", "14", "-", "a"]);
+    }
+}
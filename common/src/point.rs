@@ -0,0 +1,178 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::error::AocError;
+
+/// A position (or offset) on a 2D grid. As in the puzzle maps, `x` grows to the right and
+/// `y` grows downwards, so `UP` is `(0, -1)`; puzzles where `y` grows upwards just use
+/// the arithmetic and ignore the names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+    pub const RIGHT: Point = Point::new(1, 0);
+
+    /// The four orthogonal directions, clockwise from up.
+    pub const DIRECTIONS_4: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+
+    /// All eight directions including diagonals, clockwise from up.
+    pub const DIRECTIONS_8: [Point; 8] = [
+        Point::UP,
+        Point::new(1, -1),
+        Point::RIGHT,
+        Point::new(1, 1),
+        Point::DOWN,
+        Point::new(-1, 1),
+        Point::LEFT,
+        Point::new(-1, -1),
+    ];
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The orthogonally adjacent points.
+    pub fn neighbors_4(self) -> impl Iterator<Item = Point> {
+        Point::DIRECTIONS_4.into_iter().map(move |d| self + d)
+    }
+
+    /// The adjacent points, including diagonals.
+    pub fn neighbors_8(self) -> impl Iterator<Item = Point> {
+        Point::DIRECTIONS_8.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, n: i32) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Parse `x,y`, as in `498,4`.
+impl FromStr for Point {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Point, AocError> {
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| AocError::parse(s, "Expected a position like x,y"))?;
+        let coordinate = |c: &str| {
+            let c = c.trim();
+            c.parse::<i32>().map_err(|e| AocError::parse(c, &e.to_string()))
+        };
+        Ok(Point::new(coordinate(x)?, coordinate(y)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let p = Point::new(3, -2);
+        assert_eq!(p + Point::RIGHT, Point::new(4, -2));
+        assert_eq!(p - Point::new(1, 1), Point::new(2, -3));
+        assert_eq!(-p, Point::new(-3, 2));
+        assert_eq!(Point::DOWN * 3, Point::new(0, 3));
+        let mut q = p;
+        q += Point::UP;
+        q -= Point::LEFT;
+        assert_eq!(q, Point::new(4, -3));
+        assert_eq!(p.manhattan(Point::ORIGIN), 5);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point::new(5, 5);
+        let neighbors: Vec<Point> = p.neighbors_4().collect();
+        assert_eq!(
+            neighbors,
+            vec![Point::new(5, 4), Point::new(6, 5), Point::new(5, 6), Point::new(4, 5)]
+        );
+        assert_eq!(p.neighbors_8().count(), 8);
+        assert!(p.neighbors_8().all(|n| n != p && (n - p).x.abs() <= 1 && (n - p).y.abs() <= 1));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("498,4".parse::<Point>(), Ok(Point::new(498, 4)));
+        assert_eq!("-2, 15".parse::<Point>(), Ok(Point::new(-2, 15)));
+        assert_eq!(
+            "50x,4".parse::<Point>(),
+            Err(AocError::parse("50x", "invalid digit found in string"))
+        );
+        assert!("498".parse::<Point>().is_err());
+        assert_eq!(Point::new(498, 4).to_string(), "498,4");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_display_round_trips(x in any::<i32>(), y in any::<i32>()) {
+            let p = Point::new(x, y);
+            prop_assert_eq!(p.to_string().parse::<Point>(), Ok(p));
+        }
+
+        #[test]
+        fn prop_from_str_never_panics(s in "\\PC*") {
+            let _ = s.parse::<Point>();
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use std::fmt;
+use std::io::Read;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::logging::{init_logging, take_verbosity};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl FromStr for Part {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Part, AocError> {
+        match s.to_lowercase().as_str() {
+            "a" => Ok(Part::A),
+            "b" => Ok(Part::B),
+            _ => Err(AocError::Usage(format!("Invalid part: {}", s))),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => f.pad("a"),
+            Part::B => f.pad("b"),
+        }
+    }
+}
+
+/// A day's puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(contents: &str) -> AocResult<Self::Input>;
+
+    fn part_a(input: &Self::Input) -> AocResult<Answer>;
+
+    fn part_b(input: &Self::Input) -> AocResult<Answer>;
+
+    /// A slow but obviously correct solution to one part, straight from the input text, to
+    /// check the fast one against. Days without one leave it unimplemented.
+    fn reference(_contents: &str, _part: Part) -> AocResult<Answer> {
+        Err(AocError::Unimplemented)
+    }
+
+    fn solve_part(input: &Self::Input, part: Part) -> AocResult<Answer> {
+        match part {
+            Part::A => Self::part_a(input),
+            Part::B => Self::part_b(input),
+        }
+    }
+
+    /// Parse `contents` once, then run each of `parts` against it. A parse failure fails
+    /// everything; a failure in one part doesn't affect the others.
+    fn solve(contents: &str, parts: &[Part]) -> AocResult<Vec<AocResult<Answer>>> {
+        let input = Self::parse(contents)?;
+        Ok(parts.iter().map(|part| Self::solve_part(&input, *part)).collect())
+    }
+}
+
+/// Which parts to run, against which inputs. An input of `-` means stdin.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub parts: Vec<Part>,
+    pub input_files: Vec<String>,
+}
+
+/// Parse `a`, `b` or `both`.
+pub fn parse_parts(s: &str) -> AocResult<Vec<Part>> {
+    match s.to_lowercase().as_str() {
+        "both" => Ok(vec![Part::A, Part::B]),
+        _ => Ok(vec![Part::from_str(s)?]),
+    }
+}
+
+/// Parse `<a|b|both> <input>...`, where the part can also be given anywhere as
+/// `--part <a|b|both>`.
+pub fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let args = parse_run_args_without_input(args)?;
+    if args.input_files.is_empty() {
+        return Err(String::from("Missing input"));
+    }
+    Ok(args)
+}
+
+/// Like `parse_run_args`, but the inputs can be left out for the caller to find.
+pub fn parse_run_args_without_input(args: &[String]) -> Result<RunArgs, String> {
+    let mut parts = None;
+    let mut positional = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--part" {
+            let value = args.next().ok_or("Missing value for --part")?;
+            parts = Some(parse_parts(value).map_err(|e| e.to_string())?);
+        } else {
+            positional.push(arg.clone());
+        }
+    }
+    let parts = match parts {
+        Some(parts) => parts,
+        None if positional.is_empty() => return Err(String::from("Missing part")),
+        None => parse_parts(&positional.remove(0)).map_err(|e| e.to_string())?,
+    };
+    if positional.iter().filter(|f| *f == "-").count() > 1 {
+        return Err(String::from("Stdin (-) can only be read once"));
+    }
+    Ok(RunArgs {
+        parts,
+        input_files: positional,
+    })
+}
+
+/// Run a single day from its own binary, e.g. `cargo run -p day08 -- a input.txt`.
+pub fn run<S: Solution>(args: impl IntoIterator<Item = String>) -> AocResult<()> {
+    let mut raw_args: Vec<String> = args.into_iter().collect();
+    init_logging(take_verbosity(&mut raw_args));
+    let program = raw_args.first().map(|s| s.as_str()).unwrap_or("day");
+    let args = parse_run_args(&raw_args[1.min(raw_args.len())..]).map_err(|e| {
+        AocError::Usage(format!("{}\nUsage: {} [-v|-vv] <a|b|both> <input>...", e, program))
+    })?;
+    run_batch(S::solve, &args)
+}
+
+/// Solve the requested parts of every input, parsing each input once. With one input and
+/// one part this just prints the answer; otherwise each answer is labelled, a failure is
+/// reported and the rest carry on, and the run fails at the end if anything did.
+pub fn run_batch<F>(solve: F, args: &RunArgs) -> AocResult<()>
+where
+    F: Fn(&str, &[Part]) -> AocResult<Vec<AocResult<Answer>>>,
+{
+    let runs = args.parts.len() * args.input_files.len();
+    if runs == 1 {
+        let contents = read_input(&args.input_files[0])?;
+        let answer = solve(&contents, &args.parts)?.remove(0)?;
+        print_answer(&answer);
+        return Ok(());
+    }
+
+    let mut failures = 0;
+    for input_file in &args.input_files {
+        let results = read_input(input_file).and_then(|contents| solve(&contents, &args.parts));
+        let results = match results {
+            Ok(results) => results,
+            Err(e) => vec![Err(e); args.parts.len()],
+        };
+        for (part, result) in args.parts.iter().zip(results) {
+            println!("== {}, part {} ==", input_name(input_file), part);
+            match result {
+                Ok(answer) => print_answer(&answer),
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("error: {}", e);
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(AocError::Solve(format!("{} of {} runs failed", failures, runs)));
+    }
+    Ok(())
+}
+
+fn input_name(path: &str) -> &str {
+    if path == "-" {
+        "stdin"
+    } else {
+        path
+    }
+}
+
+/// Read an input file, naming it in the error if that fails. `-` reads stdin instead.
+pub fn read_input(path: &str) -> AocResult<String> {
+    if path == "-" {
+        let mut contents = String::new();
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .map_err(|e| AocError::Io(format!("stdin: {}", e)))?;
+        return Ok(contents);
+    }
+    std::fs::read_to_string(path).map_err(|e| AocError::Io(format!("{}: {}", path, e)))
+}
+
+/// Report the outcome of a run on stderr and turn it into the process exit code, so
+/// that scripts can tell when something went wrong.
+pub fn exit(result: AocResult<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+pub fn print_answer(answer: &Answer) {
+    if answer.is_multiline() {
+        println!("Your answer is\n{}", answer);
+    } else {
+        println!("Your answer is {}", answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    impl Solution for Echo {
+        type Input = String;
+
+        fn parse(contents: &str) -> AocResult<String> {
+            if contents.is_empty() {
+                return Err(AocError::parse("", "empty input"));
+            }
+            Ok(contents.to_string())
+        }
+
+        fn part_a(input: &String) -> AocResult<Answer> {
+            Ok(Answer::from(format!("a:{}", input)))
+        }
+
+        fn part_b(_input: &String) -> AocResult<Answer> {
+            Err(AocError::Unimplemented)
+        }
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!(Part::from_str("a"), Ok(Part::A));
+        assert_eq!(Part::from_str("B"), Ok(Part::B));
+        assert!(Part::from_str("c").is_err());
+    }
+
+    #[test]
+    fn test_solve_parses_once_for_all_parts() {
+        let results = Echo::solve("x", &[Part::A, Part::B]).unwrap();
+        assert_eq!(results[0], Ok(Answer::from("a:x")));
+        assert_eq!(results[1], Err(AocError::Unimplemented));
+        assert!(Echo::solve("", &[Part::A]).is_err());
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        assert_eq!(
+            parse_run_args(&strings(&["b", "x.txt", "-"])),
+            Ok(RunArgs {
+                parts: vec![Part::B],
+                input_files: strings(&["x.txt", "-"]),
+            })
+        );
+        assert_eq!(
+            parse_run_args(&strings(&["x.txt", "--part", "both", "y.txt"])),
+            Ok(RunArgs {
+                parts: vec![Part::A, Part::B],
+                input_files: strings(&["x.txt", "y.txt"]),
+            })
+        );
+        assert!(parse_run_args(&strings(&["a"])).is_err());
+        assert_eq!(
+            parse_run_args_without_input(&strings(&["a"])).map(|args| args.input_files),
+            Ok(vec![])
+        );
+        assert!(parse_run_args(&strings(&["c", "x.txt"])).is_err());
+        assert!(parse_run_args(&strings(&["a", "-", "-"])).is_err());
+    }
+
+    #[test]
+    fn test_run_batch_reports_failures() {
+        let args = RunArgs {
+            parts: vec![Part::A, Part::B],
+            input_files: strings(&["Cargo.toml"]),
+        };
+        assert_eq!(
+            run_batch(Echo::solve, &args),
+            Err(AocError::Solve(String::from("1 of 2 runs failed")))
+        );
+    }
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// Parsing may fail, but must never panic.
+fuzz_target!(|input: &str| {
+    let _ = day02::Day02::parse(input);
+});
@@ -1,3 +1,5 @@
-pub fn main(contents: String) -> Result<String, String> {
-    Ok(String::from("TODO"))
-}
\ No newline at end of file
+use common::{AocError, AocResult, Answer};
+
+pub fn main(_input: &[String]) -> AocResult<Answer> {
+    Err(AocError::Unimplemented)
+}
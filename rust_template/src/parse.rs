@@ -0,0 +1,18 @@
+use common::{parse_lines, AocResult};
+
+pub fn parse(contents: &str) -> AocResult<Vec<String>> {
+    parse_lines(contents, |line| Ok(line.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Paste the puzzle's example here; test_input.txt holds it for the end-to-end tests.
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn test_parse_example() {
+        assert_eq!(parse(EXAMPLE).map(|lines| lines.len()), Ok(EXAMPLE.lines().count()));
+    }
+}